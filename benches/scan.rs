@@ -0,0 +1,152 @@
+//! Scan throughput over a generated mods folder: a single worker thread
+//! against the default thread pool of `scan_mods_directory`. Every iteration
+//! starts from an empty scan cache, so all jars are actually parsed.
+//!
+//! A second group times the regex fallback for unrepairable metadata with
+//! the shared precompiled patterns against compiling them on every call, as
+//! the scanner did before.
+//!
+//! Run with `cargo bench --bench scan`. The corpus is written once to the
+//! system temp directory and reused by later runs.
+
+#![allow(dead_code)]
+
+#[path = "../src/category.rs"]
+mod category;
+#[path = "../src/filter.rs"]
+mod filter;
+#[path = "../src/lang.rs"]
+mod lang;
+#[path = "../src/lenient_json.rs"]
+mod lenient_json;
+#[path = "../src/scanner.rs"]
+mod scanner;
+#[path = "../src/version.rs"]
+mod version;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use regex::Regex;
+use scanner::ScanCache;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+const JAR_COUNT: usize = 400;
+
+/// Deterministic filler so jars have realistic size and don't compress to nothing.
+fn filler(seed: usize, len: usize) -> Vec<u8> {
+    let mut x = seed as u64 ^ 0x9e37_79b9_7f4a_7c15;
+    (0..len)
+        .map(|_| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x as u8
+        })
+        .collect()
+}
+
+fn write_jar(path: &Path, i: usize) -> zip::result::ZipResult<()> {
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(path)?);
+    let opts = SimpleFileOptions::default();
+
+    // Every fourth mod has the comments and trailing commas that
+    // `lenient_json::repair` has to fix before the metadata parses.
+    let meta = if i.is_multiple_of(4) {
+        format!(
+            "{{\n  // generated\n  \"schemaVersion\": 1,\n  \"id\": \"mod_{i}\",\n  \"name\": \"Mod {i}\",\n  \"version\": \"1.{i}.0+mc1.21.1\",\n  \"depends\": {{ \"minecraft\": \"1.21.x\", }},\n}}"
+        )
+    } else {
+        format!(
+            "{{\"schemaVersion\": 1, \"id\": \"mod_{i}\", \"name\": \"Mod {i}\", \"version\": \"1.{i}.0\", \"depends\": {{\"minecraft\": \"1.21.x\"}}}}"
+        )
+    };
+    zip.start_file("fabric.mod.json", opts)?;
+    zip.write_all(meta.as_bytes())?;
+
+    for c in 0..20 {
+        zip.start_file(format!("com/example/mod{i}/Class{c}.class"), opts)?;
+        zip.write_all(&filler(i * 100 + c, 8 * 1024))?;
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+fn corpus_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join("porovnavac-bench-corpus");
+    let complete = dir.join(format!("mod_{:04}.jar", JAR_COUNT - 1));
+    if !complete.exists() {
+        std::fs::create_dir_all(&dir).expect("create corpus dir");
+        for i in 0..JAR_COUNT {
+            write_jar(&dir.join(format!("mod_{i:04}.jar")), i).expect("write corpus jar");
+        }
+    }
+    dir
+}
+
+fn bench_scan(c: &mut Criterion) {
+    let dir = corpus_dir();
+    let threads = scanner::default_scan_threads();
+
+    let mut group = c.benchmark_group("scan_mods_directory");
+    group.sample_size(10);
+    group.bench_function("1 thread", |b| {
+        b.iter(|| scanner::scan_mods_directory_with_threads(&dir, &mut ScanCache::default(), 1))
+    });
+    group.bench_function(format!("{threads} threads"), |b| {
+        b.iter(|| {
+            scanner::scan_mods_directory_with_threads(&dir, &mut ScanCache::default(), threads)
+        })
+    });
+    group.finish();
+}
+
+/// `scanner::regex_fallback` with the patterns compiled on every call.
+fn regex_fallback_uncached(txt: &str, id_key: &str) -> Option<(String, String, String)> {
+    let grab = |key: &str| -> Option<String> {
+        let re = Regex::new(&format!(r#""{key}"\s*:\s*"([^"]+)""#)).ok()?;
+        re.captures(txt).map(|c| c[1].to_string())
+    };
+    let (id, name, version) = (grab(id_key), grab("name"), grab("version"));
+    (id.is_some() || name.is_some() || version.is_some()).then(|| {
+        (
+            id.unwrap_or_default(),
+            name.unwrap_or_default(),
+            version.unwrap_or_default(),
+        )
+    })
+}
+
+fn bench_regex_fallback(c: &mut Criterion) {
+    // Metadata broken beyond what the lenient reader repairs.
+    let texts: Vec<String> = (0..JAR_COUNT)
+        .map(|i| {
+            format!("{{\"id\": \"mod_{i}\" \"name\": \"Mod {i}\", \"version\": \"1.{i}.0\" depends: ???")
+        })
+        .collect();
+    assert!(texts
+        .iter()
+        .all(|t| scanner::regex_fallback(t, "id") == regex_fallback_uncached(t, "id")));
+
+    let mut group = c.benchmark_group("regex_fallback");
+    group.bench_function("compiled once", |b| {
+        b.iter(|| {
+            texts
+                .iter()
+                .filter_map(|t| scanner::regex_fallback(t, "id"))
+                .count()
+        })
+    });
+    group.bench_function("compiled per call", |b| {
+        b.iter(|| {
+            texts
+                .iter()
+                .filter_map(|t| regex_fallback_uncached(t, "id"))
+                .count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_scan, bench_regex_fallback);
+criterion_main!(benches);
@@ -0,0 +1,119 @@
+use crate::filter::rule_pattern;
+use crate::lenient_json;
+use crate::scanner::ModData;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// File name of the per-profile category mapping, stored next to the
+/// snapshots.
+pub const CATEGORY_FILE: &str = "porovnavac.categories.json";
+
+/// A glob or regex rule as written in the category file.
+#[derive(Deserialize)]
+struct RuleRepr {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    regex: Option<String>,
+    category: String,
+}
+
+#[derive(Deserialize)]
+struct CategoryFile {
+    /// Exact mod id → category.
+    #[serde(default)]
+    mods: BTreeMap<String, String>,
+    #[serde(default)]
+    rules: Vec<RuleRepr>,
+}
+
+/// Mod id → category, for grouping the changelog. Exact ids win over
+/// rules; rules are checked in file order.
+#[derive(Debug, Clone, Default)]
+pub struct CategoryMap {
+    mods: HashMap<String, String>,
+    rules: Vec<(Regex, String)>,
+}
+
+impl CategoryMap {
+    /// Loads the category file. A missing file means no categories; like
+    /// the filter file, broken JSON or an invalid pattern is an error.
+    pub fn load(path: &Path) -> Result<CategoryMap, String> {
+        match std::fs::read_to_string(path) {
+            Ok(txt) => CategoryMap::parse(&txt),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(CategoryMap::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn parse(txt: &str) -> Result<CategoryMap, String> {
+        let file: CategoryFile =
+            serde_json::from_str(&lenient_json::repair(txt).text).map_err(|e| e.to_string())?;
+        let rules = file
+            .rules
+            .into_iter()
+            .map(|rule| {
+                let pattern = rule_pattern(rule.id.as_deref(), rule.regex.as_deref())?;
+                Ok((pattern, rule.category))
+            })
+            .collect::<Result<_, String>>()?;
+        let mods = file
+            .mods
+            .into_iter()
+            .map(|(id, category)| (id.to_lowercase(), category))
+            .collect();
+        Ok(CategoryMap { mods, rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mods.is_empty() && self.rules.is_empty()
+    }
+
+    pub fn category(&self, id: &str) -> Option<&str> {
+        if let Some(category) = self.mods.get(&id.to_lowercase()) {
+            return Some(category);
+        }
+        self.rules
+            .iter()
+            .find(|(pattern, _)| pattern.is_match(id))
+            .map(|(_, category)| category.as_str())
+    }
+
+    /// Mods of `mods` no entry or rule covers, sorted by name, so the
+    /// mapping can be kept complete.
+    pub fn uncategorized<'a>(&self, mods: &'a [ModData]) -> Vec<&'a ModData> {
+        let mut out: Vec<&ModData> = mods
+            .iter()
+            .filter(|m| self.category(&m.id).is_none())
+            .collect();
+        out.sort_by_key(|m| m.name.to_lowercase());
+        out
+    }
+
+    /// Split `items` by category: named categories alphabetically, then the
+    /// uncategorized ones under `None`. Order within a group is kept.
+    pub fn group<T>(
+        &self,
+        items: impl IntoIterator<Item = T>,
+        id: impl Fn(&T) -> &str,
+    ) -> Vec<(Option<&str>, Vec<T>)> {
+        let mut named: BTreeMap<&str, Vec<T>> = BTreeMap::new();
+        let mut rest = Vec::new();
+        for item in items {
+            match self.category(id(&item)) {
+                Some(category) => named.entry(category).or_default().push(item),
+                None => rest.push(item),
+            }
+        }
+        let mut groups: Vec<(Option<&str>, Vec<T>)> = named
+            .into_iter()
+            .map(|(c, items)| (Some(c), items))
+            .collect();
+        if !rest.is_empty() {
+            groups.push((None, rest));
+        }
+        groups
+    }
+}
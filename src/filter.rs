@@ -0,0 +1,512 @@
+use crate::lenient_json;
+use crate::scanner::Changes;
+use regex::Regex;
+use serde::Deserialize;
+use std::path::Path;
+
+/// File name of the per-profile filter rules, stored next to the snapshots.
+pub const FILTER_FILE: &str = "porovnavac.filters.json";
+
+/// What a filter rule does with the changes of the mods it matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterAction {
+    /// Leave the mod out of every list and count.
+    Hide,
+    /// List the mod in a collapsed "libraries" section.
+    Library,
+    /// Keep the mod only in the internal changelog.
+    Internal,
+}
+
+/// A rule as written in the filter file: an id glob (`fabric-*`) or a regex,
+/// and the action for matching mods.
+#[derive(Deserialize)]
+struct RuleRepr {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    regex: Option<String>,
+    action: FilterAction,
+}
+
+#[derive(Deserialize)]
+struct FilterFile {
+    #[serde(default)]
+    rules: Vec<RuleRepr>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: Regex,
+    action: FilterAction,
+}
+
+/// Per-profile rules that hide mods or move them out of the main changelog,
+/// checked in file order; the first rule matching a mod id wins.
+#[derive(Debug, Clone, Default)]
+pub struct ModFilter {
+    rules: Vec<Rule>,
+}
+
+impl ModFilter {
+    /// Loads the filter file. A missing file means no rules; broken JSON or
+    /// an invalid pattern is an error, so a typo doesn't silently show
+    /// everything. Comments and trailing commas are accepted.
+    pub fn load(path: &Path) -> Result<ModFilter, String> {
+        match std::fs::read_to_string(path) {
+            Ok(txt) => ModFilter::parse(&txt),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ModFilter::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn parse(txt: &str) -> Result<ModFilter, String> {
+        let file: FilterFile =
+            serde_json::from_str(&lenient_json::repair(txt).text).map_err(|e| e.to_string())?;
+        let rules = file
+            .rules
+            .into_iter()
+            .map(|rule| {
+                Ok(Rule {
+                    pattern: rule_pattern(rule.id.as_deref(), rule.regex.as_deref())?,
+                    action: rule.action,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(ModFilter { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn action(&self, id: &str) -> Option<FilterAction> {
+        self.rules
+            .iter()
+            .find(|rule| rule.pattern.is_match(id))
+            .map(|rule| rule.action)
+    }
+
+    /// Sort `changes` by the rules: hidden mods are dropped (from `unchanged`
+    /// and `currently_disabled` too), library and internal ones move to
+    /// [`Changes::libraries`] and [`Changes::internal`]. Pairs are judged by
+    /// the new mod, bundled mods by their own id.
+    pub fn apply(&self, changes: &mut Changes) {
+        if self.is_empty() {
+            return;
+        }
+        let mut libraries = Changes::default();
+        let mut internal = Changes::default();
+        let by_id = |id: &str| self.action(id);
+
+        route(
+            &mut changes.added,
+            &mut libraries.added,
+            &mut internal.added,
+            |m| by_id(&m.id),
+        );
+        route(
+            &mut changes.removed,
+            &mut libraries.removed,
+            &mut internal.removed,
+            |m| by_id(&m.id),
+        );
+        route(
+            &mut changes.updated,
+            &mut libraries.updated,
+            &mut internal.updated,
+            |m| by_id(&m.id),
+        );
+        route(
+            &mut changes.replaced,
+            &mut libraries.replaced,
+            &mut internal.replaced,
+            |r| by_id(&r.new.id),
+        );
+        route(
+            &mut changes.suffix_only,
+            &mut libraries.suffix_only,
+            &mut internal.suffix_only,
+            |m| by_id(&m.id),
+        );
+        route(
+            &mut changes.rebuilt,
+            &mut libraries.rebuilt,
+            &mut internal.rebuilt,
+            |m| by_id(&m.id),
+        );
+        route(
+            &mut changes.loader_changed,
+            &mut libraries.loader_changed,
+            &mut internal.loader_changed,
+            |c| by_id(&c.id),
+        );
+        route(
+            &mut changes.environment_changed,
+            &mut libraries.environment_changed,
+            &mut internal.environment_changed,
+            |c| by_id(&c.id),
+        );
+        route(
+            &mut changes.nested_updated,
+            &mut libraries.nested_updated,
+            &mut internal.nested_updated,
+            |n| by_id(&n.id),
+        );
+        route(
+            &mut changes.newly_disabled,
+            &mut libraries.newly_disabled,
+            &mut internal.newly_disabled,
+            |t| by_id(&t.new.id),
+        );
+        route(
+            &mut changes.newly_enabled,
+            &mut libraries.newly_enabled,
+            &mut internal.newly_enabled,
+            |t| by_id(&t.new.id),
+        );
+        route(
+            &mut changes.disabled_updated,
+            &mut libraries.disabled_updated,
+            &mut internal.disabled_updated,
+            |m| by_id(&m.id),
+        );
+        changes
+            .unchanged
+            .retain(|m| by_id(&m.id) != Some(FilterAction::Hide));
+        // The disabled list shows state rather than changes, so library mods
+        // stay in it.
+        route(
+            &mut changes.currently_disabled,
+            &mut Vec::new(),
+            &mut internal.currently_disabled,
+            |m| by_id(&m.id).filter(|action| *action != FilterAction::Library),
+        );
+
+        if libraries.total_changes() > 0 {
+            changes.libraries = Some(Box::new(libraries));
+        }
+        if internal.total_changes() > 0 || !internal.currently_disabled.is_empty() {
+            changes.internal = Some(Box::new(internal));
+        }
+    }
+}
+
+/// Move every item of `items` that a rule matches to the list its action
+/// names, dropping hidden ones.
+fn route<T>(
+    items: &mut Vec<T>,
+    libraries: &mut Vec<T>,
+    internal: &mut Vec<T>,
+    action: impl Fn(&T) -> Option<FilterAction>,
+) {
+    for item in std::mem::take(items) {
+        match action(&item) {
+            None => items.push(item),
+            Some(FilterAction::Hide) => {}
+            Some(FilterAction::Library) => libraries.push(item),
+            Some(FilterAction::Internal) => internal.push(item),
+        }
+    }
+}
+
+/// The pattern of a rule that names either an id glob or a regex, as used
+/// by the filter and category files.
+pub fn rule_pattern(id: Option<&str>, regex: Option<&str>) -> Result<Regex, String> {
+    let pattern = match (id, regex) {
+        (Some(glob), None) => glob_regex(glob),
+        (None, Some(re)) => Regex::new(re),
+        _ => return Err("every rule needs exactly one of \"id\" or \"regex\"".to_string()),
+    };
+    pattern.map_err(|e| e.to_string())
+}
+
+/// Compile an id glob into an anchored, case-insensitive regex: `*` matches
+/// any run of characters and `?` a single one.
+pub fn glob_regex(glob: &str) -> Result<Regex, regex::Error> {
+    let mut re = String::from("(?i)^");
+    for ch in glob.chars() {
+        match ch {
+            '*' => re.push_str(".*"),
+            '?' => re.push('.'),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    Regex::new(&re)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn glob_is_escaped_and_case_insensitive() {
+        let re = super::glob_regex("Fabric-*").unwrap();
+        assert!(re.is_match("fabric-api"));
+        assert!(re.is_match("FABRIC-"));
+        assert!(!re.is_match("xfabric-api"));
+
+        let re = super::glob_regex("mod?").unwrap();
+        assert!(re.is_match("mod1"));
+        assert!(!re.is_match("mod"));
+        assert!(!re.is_match("mod12"));
+
+        // Regex metacharacters are taken literally.
+        let re = super::glob_regex("a.b+(c)").unwrap();
+        assert!(re.is_match("A.B+(C)"));
+        assert!(!re.is_match("axbb(c)"));
+    }
+
+    #[test]
+    fn rule_needs_exactly_one_pattern() {
+        assert!(super::rule_pattern(Some("a*"), None).is_ok());
+        assert!(super::rule_pattern(None, Some("^a.*$")).is_ok());
+        assert!(super::rule_pattern(None, None).is_err());
+        assert!(super::rule_pattern(Some("a*"), Some("^a")).is_err());
+        assert!(super::rule_pattern(None, Some("(")).is_err());
+
+        assert!(super::ModFilter::parse(r#"{"rules": [{"action": "hide"}]}"#).is_err());
+        assert!(super::ModFilter::parse(r#"{"rules": [{"id": "a", "action": "drop"}]}"#).is_err());
+        assert!(super::ModFilter::parse("{}").unwrap().is_empty());
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        use super::FilterAction;
+
+        let filter = super::ModFilter::parse(
+            r#"{
+                // Comments and trailing commas are fine.
+                "rules": [
+                    {"id": "fabric-api", "action": "library"},
+                    {"id": "fabric-*", "action": "hide"},
+                    {"regex": "^debug", "action": "internal"},
+                    {"id": "*", "action": "library"},
+                ],
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(filter.action("fabric-api"), Some(FilterAction::Library));
+        assert_eq!(filter.action("Fabric-API"), Some(FilterAction::Library));
+        assert_eq!(filter.action("fabric-renderer"), Some(FilterAction::Hide));
+        assert_eq!(filter.action("debugify"), Some(FilterAction::Internal));
+        assert_eq!(filter.action("sodium"), Some(FilterAction::Library));
+
+        let filter = super::ModFilter::parse(
+            r#"{"rules": [{"id": "fabric-*", "action": "hide"}, {"id": "fabric-api", "action": "library"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(filter.action("fabric-api"), Some(FilterAction::Hide));
+        assert_eq!(filter.action("sodium"), None);
+    }
+
+    #[test]
+    fn apply_routes_every_list() {
+        use crate::scanner::{
+            Changes, Environment, EnvironmentChange, LoaderChange, MatchReason, ModData,
+            NestedUpdate, ReplacedMod, ToggledMod, UpdatedMod,
+        };
+        use crate::version::UpdateKind;
+
+        fn mod_data(id: &str) -> ModData {
+            serde_json::from_value(serde_json::json!({
+                "filename": format!("{id}.jar"),
+                "id": id,
+                "name": id,
+                "version": "1.0.0",
+                "loader": "fabric",
+                "disabled": false,
+            }))
+            .unwrap()
+        }
+        fn updated(id: &str) -> UpdatedMod {
+            UpdatedMod {
+                id: id.to_string(),
+                name: id.to_string(),
+                old_version: "1.0.0".to_string(),
+                new_version: "1.1.0".to_string(),
+                filename: format!("{id}.jar"),
+                kind: UpdateKind::Minor,
+            }
+        }
+        fn toggled(id: &str) -> ToggledMod {
+            ToggledMod {
+                old: mod_data(id),
+                new: mod_data(id),
+                version_changed: false,
+            }
+        }
+        /// The ids in every list, labelled so a failure names the list.
+        fn ids(c: &Changes) -> Vec<(&'static str, Vec<String>)> {
+            fn of<T>(items: &[T], id: impl Fn(&T) -> &str) -> Vec<String> {
+                items.iter().map(|item| id(item).to_string()).collect()
+            }
+            vec![
+                ("added", of(&c.added, |m| &m.id)),
+                ("removed", of(&c.removed, |m| &m.id)),
+                ("updated", of(&c.updated, |m| &m.id)),
+                ("replaced", of(&c.replaced, |r| &r.new.id)),
+                ("suffix_only", of(&c.suffix_only, |m| &m.id)),
+                ("rebuilt", of(&c.rebuilt, |m| &m.id)),
+                ("loader_changed", of(&c.loader_changed, |l| &l.id)),
+                ("environment_changed", of(&c.environment_changed, |e| &e.id)),
+                ("nested_updated", of(&c.nested_updated, |n| &n.id)),
+                ("newly_disabled", of(&c.newly_disabled, |t| &t.new.id)),
+                ("newly_enabled", of(&c.newly_enabled, |t| &t.new.id)),
+                ("disabled_updated", of(&c.disabled_updated, |m| &m.id)),
+            ]
+        }
+        fn assert_lists(c: &Changes, expected: &[&str]) {
+            for (list, got) in ids(c) {
+                assert_eq!(got, expected, "{list}");
+            }
+        }
+
+        let mut changes = Changes::default();
+        for id in ["plain", "hidden", "library", "internal"] {
+            changes.added.push(mod_data(id));
+            changes.removed.push(mod_data(id));
+            changes.updated.push(updated(id));
+            changes.replaced.push(ReplacedMod {
+                old: mod_data("old"),
+                new: mod_data(id),
+                matched_by: MatchReason::Name,
+            });
+            changes.suffix_only.push(updated(id));
+            changes.rebuilt.push(mod_data(id));
+            changes.loader_changed.push(LoaderChange {
+                id: id.to_string(),
+                name: id.to_string(),
+                old_loader: "fabric".to_string(),
+                new_loader: "quilt".to_string(),
+            });
+            changes.environment_changed.push(EnvironmentChange {
+                id: id.to_string(),
+                name: id.to_string(),
+                old_environment: Environment::Both,
+                new_environment: Environment::Client,
+            });
+            changes.nested_updated.push(NestedUpdate {
+                host_id: "host".to_string(),
+                host_name: "host".to_string(),
+                id: id.to_string(),
+                name: id.to_string(),
+                old_version: "1.0.0".to_string(),
+                new_version: "1.1.0".to_string(),
+            });
+            changes.newly_disabled.push(toggled(id));
+            changes.newly_enabled.push(toggled(id));
+            changes.disabled_updated.push(updated(id));
+            changes.unchanged.push(mod_data(id));
+            changes.currently_disabled.push(mod_data(id));
+        }
+
+        let filter = super::ModFilter::parse(
+            r#"{"rules": [
+                {"id": "hidden", "action": "hide"},
+                {"id": "library", "action": "library"},
+                {"id": "internal", "action": "internal"}
+            ]}"#,
+        )
+        .unwrap();
+        filter.apply(&mut changes);
+
+        let id_list = |mods: &[ModData]| mods.iter().map(|m| m.id.clone()).collect::<Vec<_>>();
+        assert_lists(&changes, &["plain"]);
+        assert_eq!(
+            id_list(&changes.unchanged),
+            ["plain", "library", "internal"]
+        );
+        assert_eq!(id_list(&changes.currently_disabled), ["plain", "library"]);
+        assert_eq!(changes.total_changes(), 12 + 12);
+
+        let libraries = changes.libraries.as_deref().unwrap();
+        assert_lists(libraries, &["library"]);
+        assert!(libraries.currently_disabled.is_empty());
+
+        let internal = changes.internal.as_deref().unwrap();
+        assert_lists(internal, &["internal"]);
+        assert_eq!(id_list(&internal.currently_disabled), ["internal"]);
+
+        let with_internal = changes.with_internal();
+        assert_lists(&with_internal, &["plain", "internal"]);
+        assert_eq!(
+            id_list(&with_internal.currently_disabled),
+            ["plain", "library", "internal"]
+        );
+        assert!(with_internal.internal.is_none());
+        assert!(with_internal.libraries.is_some());
+    }
+
+    #[test]
+    fn server_changelog_follows_the_filter() {
+        use crate::lang::Lang;
+        use crate::scanner::{
+            compare_snapshots, generate_server_markdown, CompareOptions, Snapshot,
+        };
+
+        fn snapshot(mods: &[(&str, &str)]) -> Snapshot {
+            let active: Vec<_> = mods
+                .iter()
+                .map(|(id, version)| {
+                    serde_json::json!({
+                        "filename": format!("{id}-{version}.jar"),
+                        "id": id,
+                        "name": id,
+                        "version": version,
+                        "loader": "fabric",
+                        "disabled": false,
+                    })
+                })
+                .collect();
+            serde_json::from_value(serde_json::json!({
+                "timestamp": "2026-01-01_00-00-00",
+                "mods_dir": "mods",
+                "active": active,
+                "disabled": [],
+                "failed": [],
+                "stats": {"total": mods.len(), "active": mods.len(), "disabled": 0, "failed": 0},
+            }))
+            .unwrap()
+        }
+
+        let old = snapshot(&[("sodium", "1.0.0"), ("fabric-api", "1.0.0")]);
+        let new = snapshot(&[
+            ("sodium", "1.1.0"),
+            ("fabric-api", "1.1.0"),
+            ("debugify", "1.0.0"),
+        ]);
+        let options = CompareOptions {
+            filter: super::ModFilter::parse(
+                r#"{"rules": [
+                    {"id": "debugify", "action": "internal"},
+                    {"id": "fabric-*", "action": "library"}
+                ]}"#,
+            )
+            .unwrap(),
+            ..CompareOptions::default()
+        };
+        let changes = compare_snapshots(&old, &new, &options);
+        assert!(changes.internal.is_some() && changes.libraries.is_some());
+
+        let md = generate_server_markdown("Pack", &changes, &new, Some(&old), Lang::En);
+        assert!(md.contains("`sodium`"), "{md}");
+        assert!(!md.contains("debugify"), "{md}");
+        assert!(!md.contains("fabric-api"), "{md}");
+    }
+
+    #[test]
+    fn empty_filter_leaves_changes_alone() {
+        let mut changes = crate::scanner::Changes::default();
+        changes.unchanged.push(
+            serde_json::from_value(serde_json::json!({
+                "filename": "a.jar", "id": "a", "name": "a",
+                "version": "1", "loader": "fabric", "disabled": false,
+            }))
+            .unwrap(),
+        );
+        super::ModFilter::default().apply(&mut changes);
+        assert_eq!(changes.unchanged.len(), 1);
+        assert!(changes.libraries.is_none() && changes.internal.is_none());
+    }
+}
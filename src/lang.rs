@@ -88,6 +88,12 @@ impl T {
     pub fn load_pack_version(l: Lang) -> &'static str { get(l, "load_pack_version") }
     pub fn save_pack_version(l: Lang) -> &'static str { get(l, "save_pack_version") }
     pub fn force_new(l: Lang) -> &'static str { get(l, "force_new") }
+    pub fn group_updates(l: Lang) -> &'static str { get(l, "group_updates") }
+    pub fn normalization_label(l: Lang) -> &'static str { get(l, "normalization_label") }
+    pub fn ignore_build_metadata(l: Lang) -> &'static str { get(l, "ignore_build_metadata") }
+    pub fn ignore_mc_version(l: Lang) -> &'static str { get(l, "ignore_mc_version") }
+    pub fn ignore_loader(l: Lang) -> &'static str { get(l, "ignore_loader") }
+    pub fn list_suffix_only(l: Lang) -> &'static str { get(l, "list_suffix_only") }
     pub fn name_preview(l: Lang) -> &'static str { get(l, "name_preview") }
     pub fn scan_button(l: Lang) -> &'static str { get(l, "scan_button") }
     pub fn scanning_in_progress(l: Lang) -> &'static str { get(l, "scanning_in_progress") }
@@ -100,6 +106,26 @@ impl T {
     pub fn active(l: Lang) -> &'static str { get(l, "active") }
     pub fn disabled(l: Lang) -> &'static str { get(l, "disabled") }
     pub fn read_errors(l: Lang) -> &'static str { get(l, "read_errors") }
+    pub fn read_errors_heading(l: Lang) -> &'static str { get(l, "read_errors_heading") }
+    pub fn failure_not_zip(l: Lang) -> &'static str { get(l, "failure_not_zip") }
+    pub fn failure_corrupt(l: Lang) -> &'static str { get(l, "failure_corrupt") }
+    pub fn failure_io(l: Lang) -> &'static str { get(l, "failure_io") }
+    pub fn failure_no_metadata(l: Lang) -> &'static str { get(l, "failure_no_metadata") }
+    pub fn failure_unparseable(l: Lang) -> &'static str { get(l, "failure_unparseable") }
+    pub fn failure_crashed(l: Lang) -> &'static str { get(l, "failure_crashed") }
+    pub fn failure_unknown(l: Lang) -> &'static str { get(l, "failure_unknown") }
+    pub fn match_provides(l: Lang) -> &'static str { get(l, "match_provides") }
+    pub fn match_hash(l: Lang) -> &'static str { get(l, "match_hash") }
+    pub fn match_name(l: Lang) -> &'static str { get(l, "match_name") }
+    pub fn match_filename(l: Lang) -> &'static str { get(l, "match_filename") }
+    pub fn env_unknown(l: Lang) -> &'static str { get(l, "env_unknown") }
+    pub fn env_both(l: Lang) -> &'static str { get(l, "env_both") }
+    pub fn env_client(l: Lang) -> &'static str { get(l, "env_client") }
+    pub fn env_server(l: Lang) -> &'static str { get(l, "env_server") }
+    pub fn doubt_regex(l: Lang) -> &'static str { get(l, "doubt_regex") }
+    pub fn doubt_file_stem(l: Lang) -> &'static str { get(l, "doubt_file_stem") }
+    pub fn doubt_version_filename(l: Lang) -> &'static str { get(l, "doubt_version_filename") }
+    pub fn doubt_version_unknown(l: Lang) -> &'static str { get(l, "doubt_version_unknown") }
     pub fn changes_heading(l: Lang) -> &'static str { get(l, "changes_heading") }
     pub fn no_changes(l: Lang) -> &'static str { get(l, "no_changes") }
     pub fn no_report(l: Lang) -> &'static str { get(l, "no_report") }
@@ -107,34 +133,57 @@ impl T {
     pub fn generated_markdown(l: Lang) -> &'static str { get(l, "generated_markdown") }
     pub fn copy_to_clipboard(l: Lang) -> &'static str { get(l, "copy_to_clipboard") }
     pub fn copied(l: Lang) -> &'static str { get(l, "copied") }
+    pub fn full_changelog(l: Lang) -> &'static str { get(l, "full_changelog") }
+    pub fn server_changelog(l: Lang) -> &'static str { get(l, "server_changelog") }
+    pub fn internal_changelog(l: Lang) -> &'static str { get(l, "internal_changelog") }
     pub fn history_heading(l: Lang) -> &'static str { get(l, "history_heading") }
     pub fn refresh(l: Lang) -> &'static str { get(l, "refresh") }
     pub fn no_snapshots(l: Lang) -> &'static str { get(l, "no_snapshots") }
     pub fn run_scan_for_first(l: Lang) -> &'static str { get(l, "run_scan_for_first") }
     pub fn compare_selected(l: Lang) -> &'static str { get(l, "compare_selected") }
     pub fn compare_latest_two(l: Lang) -> &'static str { get(l, "compare_latest_two") }
+    pub fn compare_range(l: Lang) -> &'static str { get(l, "compare_range") }
+    pub fn compare_range_hint(l: Lang) -> &'static str { get(l, "compare_range_hint") }
+    pub fn per_step_breakdown(l: Lang) -> &'static str { get(l, "per_step_breakdown") }
+    pub fn compare_editions_hint(l: Lang) -> &'static str { get(l, "compare_editions_hint") }
+    pub fn edition_comparison(l: Lang) -> &'static str { get(l, "edition_comparison") }
+    pub fn edition_same(l: Lang) -> &'static str { get(l, "edition_same") }
     pub fn history_pick_hint(l: Lang) -> &'static str { get(l, "history_pick_hint") }
     pub fn history_active_short(l: Lang) -> &'static str { get(l, "history_active_short") }
     pub fn delete_snapshot(l: Lang) -> &'static str { get(l, "delete_snapshot") }
     pub fn history_comparison(l: Lang) -> &'static str { get(l, "history_comparison") }
     pub fn copy_history_md(l: Lang) -> &'static str { get(l, "copy_history_md") }
     pub fn history_md_copied(l: Lang) -> &'static str { get(l, "history_md_copied") }
+    pub fn copy_history_server_md(l: Lang) -> &'static str { get(l, "copy_history_server_md") }
+    pub fn copy_history_internal_md(l: Lang) -> &'static str { get(l, "copy_history_internal_md") }
     pub fn md_date(l: Lang) -> &'static str { get(l, "md_date") }
     pub fn md_compared_with(l: Lang) -> &'static str { get(l, "md_compared_with") }
     pub fn md_disabled_reason(l: Lang) -> &'static str { get(l, "md_disabled_reason") }
     pub fn md_recommendation(l: Lang) -> &'static str { get(l, "md_recommendation") }
+    pub fn md_server_note(l: Lang) -> &'static str { get(l, "md_server_note") }
+    pub fn md_server_no_changes(l: Lang) -> &'static str { get(l, "md_server_no_changes") }
+    pub fn md_step_no_changes(l: Lang) -> &'static str { get(l, "md_step_no_changes") }
+    pub fn update_major(l: Lang) -> &'static str { get(l, "update_major") }
+    pub fn update_minor(l: Lang) -> &'static str { get(l, "update_minor") }
+    pub fn update_patch(l: Lang) -> &'static str { get(l, "update_patch") }
+    pub fn update_other(l: Lang) -> &'static str { get(l, "update_other") }
     pub fn version_config_not_found(l: Lang) -> &'static str { get(l, "version_config_not_found") }
     pub fn version_key_missing(l: Lang) -> &'static str { get(l, "version_key_missing") }
+    pub fn dependency_check(l: Lang) -> &'static str { get(l, "dependency_check") }
+    pub fn dependencies_ok(l: Lang) -> &'static str { get(l, "dependencies_ok") }
+    pub fn duplicate_ids_hint(l: Lang) -> &'static str { get(l, "duplicate_ids_hint") }
 
     // ── Formatted strings ───────────────────────────────────────────
-    pub fn scan_done(l: Lang, active: usize, disabled: usize, failed: usize, changes: usize) -> String {
+    pub fn scan_done(l: Lang, active: usize, disabled: usize, failed: usize, changes: usize, cached: usize) -> String {
         fmt(l, "scan_done", &[
             ("{active}", &active.to_string()),
             ("{disabled}", &disabled.to_string()),
             ("{failed}", &failed.to_string()),
             ("{changes}", &changes.to_string()),
+            ("{cached}", &cached.to_string()),
         ])
     }
+    pub fn scan_crashed(l: Lang) -> &'static str { get(l, "scan_crashed") }
     pub fn added(l: Lang, count: usize) -> String {
         fmt(l, "added", &[("{count}", &count.to_string())])
     }
@@ -144,6 +193,36 @@ impl T {
     pub fn updated_detail(l: Lang, name: &str, new_ver: &str, old_ver: &str) -> String {
         fmt(l, "updated_detail", &[("{name}", name), ("{new_ver}", new_ver), ("{old_ver}", old_ver)])
     }
+    pub fn downgraded(l: Lang, count: usize) -> String {
+        fmt(l, "downgraded", &[("{count}", &count.to_string())])
+    }
+    pub fn suffix_only(l: Lang, count: usize) -> String {
+        fmt(l, "suffix_only", &[("{count}", &count.to_string())])
+    }
+    pub fn rebuilt(l: Lang, count: usize) -> String {
+        fmt(l, "rebuilt", &[("{count}", &count.to_string())])
+    }
+    pub fn replaced(l: Lang, count: usize) -> String {
+        fmt(l, "replaced", &[("{count}", &count.to_string())])
+    }
+    pub fn replaced_detail(l: Lang, old: &str, old_ver: &str, new: &str, new_ver: &str, how: &str) -> String {
+        fmt(l, "replaced_detail", &[("{old}", old), ("{old_ver}", old_ver), ("{new}", new), ("{new_ver}", new_ver), ("{how}", how)])
+    }
+    pub fn platform_changed(l: Lang, count: usize) -> String {
+        fmt(l, "platform_changed", &[("{count}", &count.to_string())])
+    }
+    pub fn loader_changed_detail(l: Lang, name: &str, old: &str, new: &str) -> String {
+        fmt(l, "loader_changed_detail", &[("{name}", name), ("{old}", old), ("{new}", new)])
+    }
+    pub fn environment_changed_detail(l: Lang, name: &str, old: &str, new: &str) -> String {
+        fmt(l, "environment_changed_detail", &[("{name}", name), ("{old}", old), ("{new}", new)])
+    }
+    pub fn nested_updated(l: Lang, count: usize) -> String {
+        fmt(l, "nested_updated", &[("{count}", &count.to_string())])
+    }
+    pub fn nested_updated_detail(l: Lang, host: &str, name: &str, new_ver: &str, old_ver: &str) -> String {
+        fmt(l, "nested_updated_detail", &[("{host}", host), ("{name}", name), ("{new_ver}", new_ver), ("{old_ver}", old_ver)])
+    }
     pub fn removed(l: Lang, count: usize) -> String {
         fmt(l, "removed", &[("{count}", &count.to_string())])
     }
@@ -153,9 +232,48 @@ impl T {
     pub fn newly_enabled(l: Lang, count: usize) -> String {
         fmt(l, "newly_enabled", &[("{count}", &count.to_string())])
     }
+    pub fn disabled_updated(l: Lang, count: usize) -> String {
+        fmt(l, "disabled_updated", &[("{count}", &count.to_string())])
+    }
+    pub fn reenabled_detail(l: Lang, name: &str, new_ver: &str, old_ver: &str) -> String {
+        fmt(l, "reenabled_detail", &[("{name}", name), ("{new_ver}", new_ver), ("{old_ver}", old_ver)])
+    }
+    pub fn disabled_detail(l: Lang, name: &str, new_ver: &str, old_ver: &str) -> String {
+        fmt(l, "disabled_detail", &[("{name}", name), ("{new_ver}", new_ver), ("{old_ver}", old_ver)])
+    }
+    pub fn libraries(l: Lang, count: usize) -> String {
+        fmt(l, "libraries", &[("{count}", &count.to_string())])
+    }
+    pub fn internal_only(l: Lang, count: usize) -> String {
+        fmt(l, "internal_only", &[("{count}", &count.to_string())])
+    }
+    pub fn filter_error(l: Lang, file: &str, error: &str) -> String {
+        fmt(l, "filter_error", &[("{file}", file), ("{error}", error)])
+    }
+    pub fn category_error(l: Lang, file: &str, error: &str) -> String {
+        fmt(l, "category_error", &[("{file}", file), ("{error}", error)])
+    }
+    pub fn uncategorized_mods(l: Lang, count: usize) -> String {
+        fmt(l, "uncategorized_mods", &[("{count}", &count.to_string())])
+    }
+    pub fn uncategorized_hint(l: Lang, file: &str) -> String {
+        fmt(l, "uncategorized_hint", &[("{file}", file)])
+    }
+    pub fn uncategorized_detail(l: Lang, name: &str, id: &str) -> String {
+        fmt(l, "uncategorized_detail", &[("{name}", name), ("{id}", id)])
+    }
     pub fn unchanged_summary(l: Lang, unchanged: usize, total: usize) -> String {
         fmt(l, "unchanged_summary", &[("{unchanged}", &unchanged.to_string()), ("{total}", &total.to_string())])
     }
+    pub fn dependency_missing(l: Lang, name: &str, dep: &str, range: &str) -> String {
+        fmt(l, "dependency_missing", &[("{name}", name), ("{dep}", dep), ("{range}", range)])
+    }
+    pub fn dependency_disabled(l: Lang, name: &str, dep: &str, range: &str) -> String {
+        fmt(l, "dependency_disabled", &[("{name}", name), ("{dep}", dep), ("{range}", range)])
+    }
+    pub fn dependency_breaks(l: Lang, name: &str, other: &str, version: &str, range: &str) -> String {
+        fmt(l, "dependency_breaks", &[("{name}", name), ("{other}", other), ("{version}", version), ("{range}", range)])
+    }
     pub fn snapshots_found(l: Lang, count: usize) -> String {
         fmt(l, "snapshots_found", &[("{count}", &count.to_string())])
     }
@@ -168,6 +286,30 @@ impl T {
     pub fn history_summary(l: Lang, changes: usize, file_a: &str, file_b: &str) -> String {
         fmt(l, "history_summary", &[("{changes}", &changes.to_string()), ("{file_a}", file_a), ("{file_b}", file_b)])
     }
+    pub fn history_range_summary(l: Lang, changes: usize, steps: usize, file_a: &str, file_b: &str) -> String {
+        fmt(l, "history_range_summary", &[("{changes}", &changes.to_string()), ("{steps}", &steps.to_string()), ("{file_a}", file_a), ("{file_b}", file_b)])
+    }
+    pub fn range_steps(l: Lang, count: usize) -> String {
+        fmt(l, "range_steps", &[("{count}", &count.to_string())])
+    }
+    pub fn range_step(l: Lang, index: usize, from: &str, to: &str, count: usize) -> String {
+        fmt(l, "range_step", &[("{index}", &index.to_string()), ("{from}", from), ("{to}", to), ("{count}", &count.to_string())])
+    }
+    pub fn compare_editions(l: Lang, a: &str, b: &str) -> String {
+        fmt(l, "compare_editions", &[("{a}", a), ("{b}", b)])
+    }
+    pub fn edition_only_in(l: Lang, edition: &str, count: usize) -> String {
+        fmt(l, "edition_only_in", &[("{edition}", edition), ("{count}", &count.to_string())])
+    }
+    pub fn edition_version_diff(l: Lang, count: usize) -> String {
+        fmt(l, "edition_version_diff", &[("{count}", &count.to_string())])
+    }
+    pub fn edition_version_detail(l: Lang, name: &str, a: &str, va: &str, b: &str, vb: &str) -> String {
+        fmt(l, "edition_version_detail", &[("{name}", name), ("{a}", a), ("{va}", va), ("{b}", b), ("{vb}", vb)])
+    }
+    pub fn edition_summary(l: Lang, count: usize, file_a: &str, file_b: &str) -> String {
+        fmt(l, "edition_summary", &[("{count}", &count.to_string()), ("{file_a}", file_a), ("{file_b}", file_b)])
+    }
     pub fn version_loaded(l: Lang, version: &str) -> String {
         fmt(l, "version_loaded", &[("{version}", version)])
     }
@@ -198,6 +340,39 @@ impl T {
     pub fn md_updated_detail(l: Lang, name: &str, new_ver: &str, old_ver: &str) -> String {
         fmt(l, "md_updated_detail", &[("{name}", name), ("{new_ver}", new_ver), ("{old_ver}", old_ver)])
     }
+    pub fn md_update_group(l: Lang, kind: &str, count: usize) -> String {
+        fmt(l, "md_update_group", &[("{kind}", kind), ("{count}", &count.to_string())])
+    }
+    pub fn md_downgraded_mods(l: Lang, count: usize) -> String {
+        fmt(l, "md_downgraded_mods", &[("{count}", &count.to_string())])
+    }
+    pub fn md_suffix_only(l: Lang, count: usize) -> String {
+        fmt(l, "md_suffix_only", &[("{count}", &count.to_string())])
+    }
+    pub fn md_rebuilt_mods(l: Lang, count: usize) -> String {
+        fmt(l, "md_rebuilt_mods", &[("{count}", &count.to_string())])
+    }
+    pub fn md_replaced_mods(l: Lang, count: usize) -> String {
+        fmt(l, "md_replaced_mods", &[("{count}", &count.to_string())])
+    }
+    pub fn md_replaced_detail(l: Lang, old: &str, old_ver: &str, new: &str, new_ver: &str, how: &str) -> String {
+        fmt(l, "md_replaced_detail", &[("{old}", old), ("{old_ver}", old_ver), ("{new}", new), ("{new_ver}", new_ver), ("{how}", how)])
+    }
+    pub fn md_platform_changed(l: Lang, count: usize) -> String {
+        fmt(l, "md_platform_changed", &[("{count}", &count.to_string())])
+    }
+    pub fn md_loader_changed_detail(l: Lang, name: &str, old: &str, new: &str) -> String {
+        fmt(l, "md_loader_changed_detail", &[("{name}", name), ("{old}", old), ("{new}", new)])
+    }
+    pub fn md_environment_changed_detail(l: Lang, name: &str, old: &str, new: &str) -> String {
+        fmt(l, "md_environment_changed_detail", &[("{name}", name), ("{old}", old), ("{new}", new)])
+    }
+    pub fn md_nested_updated(l: Lang, count: usize) -> String {
+        fmt(l, "md_nested_updated", &[("{count}", &count.to_string())])
+    }
+    pub fn md_nested_updated_detail(l: Lang, host: &str, name: &str, new_ver: &str, old_ver: &str) -> String {
+        fmt(l, "md_nested_updated_detail", &[("{host}", host), ("{name}", name), ("{new_ver}", new_ver), ("{old_ver}", old_ver)])
+    }
     pub fn md_removed_mods(l: Lang, count: usize) -> String {
         fmt(l, "md_removed_mods", &[("{count}", &count.to_string())])
     }
@@ -210,13 +385,125 @@ impl T {
     pub fn md_currently_disabled(l: Lang, count: usize) -> String {
         fmt(l, "md_currently_disabled", &[("{count}", &count.to_string())])
     }
+    pub fn md_disabled_updated(l: Lang, count: usize) -> String {
+        fmt(l, "md_disabled_updated", &[("{count}", &count.to_string())])
+    }
+    pub fn md_reenabled_detail(l: Lang, name: &str, new_ver: &str, old_ver: &str) -> String {
+        fmt(l, "md_reenabled_detail", &[("{name}", name), ("{new_ver}", new_ver), ("{old_ver}", old_ver)])
+    }
+    pub fn md_disabled_detail(l: Lang, name: &str, new_ver: &str, old_ver: &str) -> String {
+        fmt(l, "md_disabled_detail", &[("{name}", name), ("{new_ver}", new_ver), ("{old_ver}", old_ver)])
+    }
+    pub fn check_these(l: Lang, count: usize) -> String {
+        fmt(l, "check_these", &[("{count}", &count.to_string())])
+    }
+    pub fn check_these_detail(l: Lang, name: &str, filename: &str, doubts: &str) -> String {
+        fmt(l, "check_these_detail", &[("{name}", name), ("{filename}", filename), ("{doubts}", doubts)])
+    }
+    pub fn duplicate_ids(l: Lang, count: usize) -> String {
+        fmt(l, "duplicate_ids", &[("{count}", &count.to_string())])
+    }
+    pub fn duplicate_id_detail(l: Lang, id: &str, files: &str) -> String {
+        fmt(l, "duplicate_id_detail", &[("{id}", id), ("{files}", files)])
+    }
+    pub fn md_duplicate_ids(l: Lang, count: usize) -> String {
+        fmt(l, "md_duplicate_ids", &[("{count}", &count.to_string())])
+    }
+    pub fn md_duplicate_id_detail(l: Lang, id: &str, files: &str) -> String {
+        fmt(l, "md_duplicate_id_detail", &[("{id}", id), ("{files}", files)])
+    }
+    pub fn md_check_these(l: Lang, count: usize) -> String {
+        fmt(l, "md_check_these", &[("{count}", &count.to_string())])
+    }
+    pub fn md_check_these_detail(l: Lang, name: &str, filename: &str, doubts: &str) -> String {
+        fmt(l, "md_check_these_detail", &[("{name}", name), ("{filename}", filename), ("{doubts}", doubts)])
+    }
     pub fn md_read_errors(l: Lang, count: usize) -> String {
         fmt(l, "md_read_errors", &[("{count}", &count.to_string())])
     }
+    pub fn read_error_group(l: Lang, reason: &str, count: usize) -> String {
+        fmt(l, "read_error_group", &[("{reason}", reason), ("{count}", &count.to_string())])
+    }
+    pub fn md_read_error_group(l: Lang, reason: &str, count: usize) -> String {
+        fmt(l, "md_read_error_group", &[("{reason}", reason), ("{count}", &count.to_string())])
+    }
     pub fn md_read_error_detail(l: Lang, filename: &str) -> String {
         fmt(l, "md_read_error_detail", &[("{filename}", filename)])
     }
+    pub fn md_read_error_detail_with(l: Lang, filename: &str, detail: &str) -> String {
+        fmt(l, "md_read_error_detail_with", &[("{filename}", filename), ("{detail}", detail)])
+    }
     pub fn md_summary(l: Lang, unchanged: usize, total: usize) -> String {
         fmt(l, "md_summary", &[("{unchanged}", &unchanged.to_string()), ("{total}", &total.to_string())])
     }
+    pub fn md_server_heading(l: Lang, display_name: &str) -> String {
+        fmt(l, "md_server_heading", &[("{name}", display_name)])
+    }
+    pub fn md_server_summary(l: Lang, total: usize) -> String {
+        fmt(l, "md_server_summary", &[("{total}", &total.to_string())])
+    }
+    pub fn md_range_steps(l: Lang, count: usize) -> String {
+        fmt(l, "md_range_steps", &[("{count}", &count.to_string())])
+    }
+    pub fn md_range_step(l: Lang, index: usize, from: &str, to: &str, count: usize) -> String {
+        fmt(l, "md_range_step", &[("{index}", &index.to_string()), ("{from}", from), ("{to}", to), ("{count}", &count.to_string())])
+    }
+    pub fn md_step_added(l: Lang, name: &str, version: &str) -> String {
+        fmt(l, "md_step_added", &[("{name}", name), ("{version}", version)])
+    }
+    pub fn md_step_updated(l: Lang, name: &str, new_ver: &str, old_ver: &str) -> String {
+        fmt(l, "md_step_updated", &[("{name}", name), ("{new_ver}", new_ver), ("{old_ver}", old_ver)])
+    }
+    pub fn md_step_removed(l: Lang, name: &str, version: &str) -> String {
+        fmt(l, "md_step_removed", &[("{name}", name), ("{version}", version)])
+    }
+    pub fn md_step_replaced(l: Lang, old: &str, new: &str, new_ver: &str) -> String {
+        fmt(l, "md_step_replaced", &[("{old}", old), ("{new}", new), ("{new_ver}", new_ver)])
+    }
+    pub fn md_step_enabled(l: Lang, name: &str, version: &str) -> String {
+        fmt(l, "md_step_enabled", &[("{name}", name), ("{version}", version)])
+    }
+    pub fn md_step_disabled(l: Lang, name: &str, version: &str) -> String {
+        fmt(l, "md_step_disabled", &[("{name}", name), ("{version}", version)])
+    }
+    pub fn md_step_nested(l: Lang, host: &str, name: &str, new_ver: &str, old_ver: &str) -> String {
+        fmt(l, "md_step_nested", &[("{host}", host), ("{name}", name), ("{new_ver}", new_ver), ("{old_ver}", old_ver)])
+    }
+    pub fn md_step_suffix_only(l: Lang, name: &str, new_ver: &str, old_ver: &str) -> String {
+        fmt(l, "md_step_suffix_only", &[("{name}", name), ("{new_ver}", new_ver), ("{old_ver}", old_ver)])
+    }
+    pub fn md_step_rebuilt(l: Lang, name: &str, version: &str) -> String {
+        fmt(l, "md_step_rebuilt", &[("{name}", name), ("{version}", version)])
+    }
+    pub fn md_step_loader(l: Lang, name: &str, old: &str, new: &str) -> String {
+        fmt(l, "md_step_loader", &[("{name}", name), ("{old}", old), ("{new}", new)])
+    }
+    pub fn md_step_environment(l: Lang, name: &str, old: &str, new: &str) -> String {
+        fmt(l, "md_step_environment", &[("{name}", name), ("{old}", old), ("{new}", new)])
+    }
+    pub fn md_step_disabled_updated(l: Lang, name: &str, new_ver: &str, old_ver: &str) -> String {
+        fmt(l, "md_step_disabled_updated", &[("{name}", name), ("{new_ver}", new_ver), ("{old_ver}", old_ver)])
+    }
+    pub fn md_libraries(l: Lang, count: usize) -> String {
+        fmt(l, "md_libraries", &[("{count}", &count.to_string())])
+    }
+    pub fn md_category(l: Lang, level: &str, category: &str, count: usize) -> String {
+        fmt(l, "md_category", &[("{level}", level), ("{category}", category), ("{count}", &count.to_string())])
+    }
+    pub fn md_uncategorized(l: Lang) -> &'static str { get(l, "md_uncategorized") }
+    pub fn md_edition_heading(l: Lang, name: &str, a: &str, b: &str) -> String {
+        fmt(l, "md_edition_heading", &[("{name}", name), ("{a}", a), ("{b}", b)])
+    }
+    pub fn md_edition_only_in(l: Lang, edition: &str, count: usize) -> String {
+        fmt(l, "md_edition_only_in", &[("{edition}", edition), ("{count}", &count.to_string())])
+    }
+    pub fn md_edition_version_diff(l: Lang, count: usize) -> String {
+        fmt(l, "md_edition_version_diff", &[("{count}", &count.to_string())])
+    }
+    pub fn md_edition_version_detail(l: Lang, name: &str, a: &str, va: &str, b: &str, vb: &str) -> String {
+        fmt(l, "md_edition_version_detail", &[("{name}", name), ("{a}", a), ("{va}", va), ("{b}", b), ("{vb}", vb)])
+    }
+    pub fn md_edition_summary(l: Lang, shared: usize, total: usize) -> String {
+        fmt(l, "md_edition_summary", &[("{shared}", &shared.to_string()), ("{total}", &total.to_string())])
+    }
 }
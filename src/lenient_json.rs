@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// A fix applied to malformed JSON so that a strict parser accepts it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Repair {
+    /// A UTF-8 byte order mark before the document.
+    Bom,
+    /// `// ...` comments.
+    LineComment,
+    /// `/* ... */` comments.
+    BlockComment,
+    /// A comma right before `}` or `]`.
+    TrailingComma,
+    /// A line break written literally inside a string.
+    RawNewline,
+    /// Other control characters: escaped inside strings (`\t`, otherwise
+    /// `\u00XX`), dropped outside.
+    ControlChar,
+}
+
+/// JSON text rewritten into strict form, with the repairs that were needed
+/// (sorted, each listed once).
+#[derive(Debug, Clone)]
+pub struct Repaired {
+    pub text: String,
+    pub repairs: Vec<Repair>,
+}
+
+/// Rewrite the lenient JSON found in mod metadata (comments, trailing commas,
+/// BOM, raw newlines and control characters) into strict JSON in a single
+/// pass. String contents are never touched apart from escaping, so a
+/// `"https://..."` value or a `/*` inside a string survives intact.
+pub fn repair(txt: &str) -> Repaired {
+    let mut repairs = BTreeSet::new();
+    let txt = match txt.strip_prefix('\u{feff}') {
+        Some(rest) => {
+            repairs.insert(Repair::Bom);
+            rest
+        }
+        None => txt,
+    };
+
+    let mut out = String::with_capacity(txt.len());
+    let mut chars = txt.chars().peekable();
+    let mut in_string = false;
+    let mut escaped = false;
+    // Byte offset in `out` of a comma that may turn out to be trailing.
+    let mut pending_comma: Option<usize> = None;
+
+    while let Some(ch) = chars.next() {
+        if in_string {
+            if escaped {
+                out.push(ch);
+                escaped = false;
+                continue;
+            }
+            match ch {
+                '\\' => {
+                    out.push(ch);
+                    escaped = true;
+                }
+                '"' => {
+                    out.push(ch);
+                    in_string = false;
+                }
+                '\n' => {
+                    out.push_str("\\n");
+                    repairs.insert(Repair::RawNewline);
+                }
+                '\r' => {
+                    repairs.insert(Repair::RawNewline);
+                }
+                '\t' => {
+                    out.push_str("\\t");
+                    repairs.insert(Repair::ControlChar);
+                }
+                c if c < ' ' => {
+                    out.push_str(&format!("\\u{:04x}", c as u32));
+                    repairs.insert(Repair::ControlChar);
+                }
+                c => out.push(c),
+            }
+            continue;
+        }
+
+        match ch {
+            '/' if chars.peek() == Some(&'/') => {
+                // Keep the line break so line numbers in parse errors still match.
+                while chars.next_if(|&c| c != '\n').is_some() {}
+                repairs.insert(Repair::LineComment);
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                repairs.insert(Repair::BlockComment);
+            }
+            ',' => {
+                pending_comma = Some(out.len());
+                out.push(ch);
+            }
+            '}' | ']' => {
+                if let Some(at) = pending_comma.take() {
+                    out.remove(at);
+                    repairs.insert(Repair::TrailingComma);
+                }
+                out.push(ch);
+            }
+            ' ' | '\t' | '\n' | '\r' => out.push(ch),
+            c if c < ' ' || c == '\u{7f}' => {
+                repairs.insert(Repair::ControlChar);
+            }
+            c => {
+                pending_comma = None;
+                in_string = c == '"';
+                out.push(c);
+            }
+        }
+    }
+
+    Repaired {
+        text: out,
+        repairs: repairs.into_iter().collect(),
+    }
+}
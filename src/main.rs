@@ -1,22 +1,47 @@
 #![windows_subsystem = "windows"]
 
+mod category;
+mod filter;
 mod lang;
+mod lenient_json;
 mod scanner;
+mod version;
 
+use category::{CategoryMap, CATEGORY_FILE};
 use eframe::egui;
+use filter::{ModFilter, FILTER_FILE};
 use lang::{Lang, T};
 use scanner::{
-    build_display_name, build_file_prefix, build_timestamped_prefix, compare_snapshots,
-    edition_slug, generate_markdown, scan_mods_directory, Changes, Snapshot,
+    build_display_name, build_file_prefix, build_timestamped_prefix, check_dependencies,
+    compare_editions, compare_range, compare_snapshots, edition_slug, find_duplicate_ids,
+    generate_edition_markdown, generate_markdown, generate_range_markdown, generate_server_markdown,
+    group_failures, low_confidence_mods, scan_mods_directory, short_timestamp, Changes,
+    CompareOptions, DependencyReport, DuplicateId, EditionDiff, MarkdownOptions, RangeStep,
+    ScanCache, ScanFailure, Snapshot, SCAN_CACHE_FILE,
 };
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::mpsc;
 use std::thread;
+use version::{Normalization, UpdateKind};
 
 #[derive(serde::Serialize, serde::Deserialize)]
 struct AppSettings {
     lang: Lang,
+    #[serde(default)]
+    normalization: Normalization,
+    #[serde(default)]
+    list_suffix_only: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            lang: Lang::Cs,
+            normalization: Normalization::default(),
+            list_suffix_only: false,
+        }
+    }
 }
 
 fn app_settings_path() -> Option<PathBuf> {
@@ -28,19 +53,18 @@ fn app_settings_path() -> Option<PathBuf> {
     )
 }
 
-fn load_saved_language() -> Lang {
+fn load_settings() -> AppSettings {
     let Some(path) = app_settings_path() else {
-        return Lang::Cs;
+        return AppSettings::default();
     };
 
     std::fs::read_to_string(path)
         .ok()
         .and_then(|txt| serde_json::from_str::<AppSettings>(&txt).ok())
-        .map(|s| s.lang)
-        .unwrap_or(Lang::Cs)
+        .unwrap_or_default()
 }
 
-fn save_language(lang: Lang) {
+fn save_settings(settings: &AppSettings) {
     let Some(path) = app_settings_path() else {
         return;
     };
@@ -51,8 +75,7 @@ fn save_language(lang: Lang) {
         }
     }
 
-    let settings = AppSettings { lang };
-    if let Ok(json) = serde_json::to_string_pretty(&settings) {
+    if let Ok(json) = serde_json::to_string_pretty(settings) {
         let _ = std::fs::write(path, json);
     }
 }
@@ -69,7 +92,8 @@ fn load_icon() -> Option<egui::IconData> {
 }
 
 fn main() -> eframe::Result<()> {
-    let startup_lang = load_saved_language();
+    let settings = load_settings();
+    let startup_lang = settings.lang;
     let mut viewport = egui::ViewportBuilder::default()
         .with_inner_size([760.0, 680.0])
         .with_min_inner_size([500.0, 400.0]);
@@ -88,7 +112,7 @@ fn main() -> eframe::Result<()> {
         options,
         Box::new(move |cc| {
             cc.egui_ctx.set_visuals(egui::Visuals::dark());
-            Ok(Box::new(App::new(startup_lang)))
+            Ok(Box::new(App::new(settings)))
         }),
     )
 }
@@ -257,12 +281,16 @@ fn find_snapshot_history(profile_dir: &PathBuf) -> Vec<SnapshotEntry> {
         for entry in rd.flatten() {
             let name = entry.file_name().to_string_lossy().to_string();
             if name.ends_with(".mods_snapshot.json") {
-                let Some(snapshot) = std::fs::read_to_string(entry.path())
+                let Some(mut snapshot) = std::fs::read_to_string(entry.path())
                     .ok()
                     .and_then(|txt| serde_json::from_str::<Snapshot>(&txt).ok())
                 else {
                     continue;
                 };
+                // Snapshots from older versions don't record duplicate ids.
+                if snapshot.duplicates.is_empty() {
+                    snapshot.duplicates = find_duplicate_ids(&snapshot.active);
+                }
 
                 entries.push(SnapshotEntry {
                     filename: name.clone(),
@@ -280,7 +308,8 @@ fn find_snapshot_history(profile_dir: &PathBuf) -> Vec<SnapshotEntry> {
 }
 
 /// Most recent snapshot of the given edition, used as the baseline for an
-/// automatic comparison. Assumes `history` is sorted newest-first.
+/// automatic comparison and for the cross-edition view. Assumes `history`
+/// is sorted newest-first.
 fn latest_snapshot_for_edition<'a>(
     history: &'a [SnapshotEntry],
     edition: &str,
@@ -299,15 +328,29 @@ struct ScanResult {
     snapshot: Snapshot,
     old_snapshot: Option<Snapshot>,
     changes: Changes,
+    dependencies: DependencyReport,
     markdown: String,
+    server_markdown: String,
+    /// Empty unless the profile's filter keeps some mods internal.
+    internal_markdown: String,
     snapshot_path: PathBuf,
     md_path: PathBuf,
+    server_md_path: PathBuf,
+    internal_md_path: PathBuf,
 }
 
 // ──────────────────────────────────────────────────────────────────────
 // App
 // ──────────────────────────────────────────────────────────────────────
 
+/// Which changelog the Markdown tab shows.
+#[derive(PartialEq, Clone, Copy)]
+enum MarkdownView {
+    Full,
+    Server,
+    Internal,
+}
+
 #[derive(PartialEq)]
 enum Tab {
     Settings,
@@ -324,6 +367,8 @@ struct App {
     pack_version: String,
     pack_version_dirty: bool,
     force_new: bool,
+    markdown_options: MarkdownOptions,
+    compare_options: CompareOptions,
     profiles: Vec<ModrinthProfile>,
     selected_profile: Option<usize>,
     scan_rx: Option<mpsc::Receiver<ScanResult>>,
@@ -332,20 +377,34 @@ struct App {
     snapshot: Option<Snapshot>,
     old_snapshot: Option<Snapshot>,
     changes: Option<Changes>,
+    dependencies: Option<DependencyReport>,
     markdown: String,
+    server_markdown: String,
+    internal_markdown: String,
+    markdown_view: MarkdownView,
     status: String,
+    /// Problems with the profile's filter or category file; the comparison
+    /// runs without the broken file.
+    profile_errors: Vec<String>,
     scan_done: bool,
     history: Vec<SnapshotEntry>,
     history_selected_a: Option<usize>,
     history_selected_b: Option<usize>,
     history_changes: Option<Changes>,
     history_markdown: String,
+    history_server_markdown: String,
+    history_internal_markdown: String,
+    /// Steps of the last range comparison; empty after a plain A → B one.
+    history_steps: Vec<RangeStep>,
+    /// Last cross-edition comparison (newest Full vs newest Lite).
+    edition_diff: Option<EditionDiff>,
+    edition_markdown: String,
 }
 
 const EDITIONS: [&str; 2] = ["Full", "Lite"];
 
 impl App {
-    fn new(startup_lang: Lang) -> Self {
+    fn new(settings: AppSettings) -> Self {
         let profiles = detect_modrinth_profiles();
         let selected = profiles
             .iter()
@@ -358,13 +417,19 @@ impl App {
             .unwrap_or_else(|| "26.1.0".to_string());
 
         Self {
-            lang: startup_lang,
+            lang: settings.lang,
             mods_dir,
             base_name: "Agonia".to_string(),
             edition_index: 0,
             pack_version,
             pack_version_dirty: false,
             force_new: false,
+            markdown_options: MarkdownOptions::default(),
+            compare_options: CompareOptions {
+                normalization: settings.normalization,
+                list_suffix_only: settings.list_suffix_only,
+                ..Default::default()
+            },
             profiles,
             selected_profile: selected,
             scan_rx: None,
@@ -373,14 +438,24 @@ impl App {
             snapshot: None,
             old_snapshot: None,
             changes: None,
+            dependencies: None,
             markdown: String::new(),
+            server_markdown: String::new(),
+            internal_markdown: String::new(),
+            markdown_view: MarkdownView::Full,
             status: String::new(),
+            profile_errors: Vec::new(),
             scan_done: false,
             history: Vec::new(),
             history_selected_a: None,
             history_selected_b: None,
             history_changes: None,
             history_markdown: String::new(),
+            history_server_markdown: String::new(),
+            history_internal_markdown: String::new(),
+            history_steps: Vec::new(),
+            edition_diff: None,
+            edition_markdown: String::new(),
         }
     }
 
@@ -388,6 +463,43 @@ impl App {
         EDITIONS[self.edition_index]
     }
 
+    fn save_settings(&self) {
+        save_settings(&AppSettings {
+            lang: self.lang,
+            normalization: self.compare_options.normalization,
+            list_suffix_only: self.compare_options.list_suffix_only,
+        });
+    }
+
+    /// Re-reads the profile's filter and category files, so edits apply
+    /// without a restart. Categories go into the Markdown options; the
+    /// returned comparison settings carry the filter.
+    fn load_profile_rules(&mut self) -> CompareOptions {
+        let l = self.l();
+        let mut options = self.compare_options.clone();
+        self.markdown_options.categories = CategoryMap::default();
+        self.profile_errors.clear();
+        if let Some(dir) = self.profile_dir() {
+            let path = dir.join(FILTER_FILE);
+            match ModFilter::load(&path) {
+                Ok(filter) => options.filter = filter,
+                Err(err) => {
+                    let err = T::filter_error(l, &path.to_string_lossy(), &err);
+                    self.profile_errors.push(err);
+                }
+            }
+            let path = dir.join(CATEGORY_FILE);
+            match CategoryMap::load(&path) {
+                Ok(categories) => self.markdown_options.categories = categories,
+                Err(err) => {
+                    let err = T::category_error(l, &path.to_string_lossy(), &err);
+                    self.profile_errors.push(err);
+                }
+            }
+        }
+        options
+    }
+
     fn profile_dir(&self) -> Option<PathBuf> {
         let p = PathBuf::from(&self.mods_dir);
         p.parent().map(|p| p.to_path_buf())
@@ -489,7 +601,7 @@ fn is_json_config(path: &std::path::Path) -> bool {
         .unwrap_or(false)
 }
 
-fn read_pack_version_from_profile(mods_path: &PathBuf) -> Option<String> {
+fn read_pack_version_from_profile(mods_path: &std::path::Path) -> Option<String> {
     let profile_dir = mods_path.parent()?;
     let path = packbranding_config_path(profile_dir);
     read_pack_version_from_config(&path).ok().flatten()
@@ -595,32 +707,53 @@ impl eframe::App for App {
         let l = self.l();
 
         if let Some(rx) = &self.scan_rx {
-            if let Ok(result) = rx.try_recv() {
-                if let Ok(json) = serde_json::to_string_pretty(&result.snapshot) {
-                    let _ = std::fs::write(&result.snapshot_path, &json);
-                }
-                let _ = std::fs::write(&result.md_path, &result.markdown);
-
-                self.status = T::scan_done(
-                    l,
-                    result.snapshot.stats.active,
-                    result.snapshot.stats.disabled,
-                    result.snapshot.stats.failed,
-                    result.changes.total_changes(),
-                );
+            match rx.try_recv() {
+                Ok(result) => {
+                    if let Ok(json) = serde_json::to_string_pretty(&result.snapshot) {
+                        let _ = std::fs::write(&result.snapshot_path, &json);
+                    }
+                    let _ = std::fs::write(&result.md_path, &result.markdown);
+                    let _ = std::fs::write(&result.server_md_path, &result.server_markdown);
+                    if !result.internal_markdown.is_empty() {
+                        let _ = std::fs::write(&result.internal_md_path, &result.internal_markdown);
+                    }
 
-                self.markdown = result.markdown;
-                self.old_snapshot = result.old_snapshot;
-                self.changes = Some(result.changes);
-                self.snapshot = Some(result.snapshot);
-                self.scan_done = true;
-                self.scanning = false;
-                self.scan_rx = None;
-                self.tab = Tab::Results;
-
-                if let Some(dir) = self.profile_dir() {
-                    self.history = find_snapshot_history(&dir);
+                    self.status = T::scan_done(
+                        l,
+                        result.snapshot.stats.active,
+                        result.snapshot.stats.disabled,
+                        result.snapshot.stats.failed,
+                        result.changes.total_changes(),
+                        result.snapshot.stats.cached,
+                    );
+
+                    self.markdown = result.markdown;
+                    self.server_markdown = result.server_markdown;
+                    self.internal_markdown = result.internal_markdown;
+                    if self.internal_markdown.is_empty() && self.markdown_view == MarkdownView::Internal {
+                        self.markdown_view = MarkdownView::Full;
+                    }
+                    self.old_snapshot = result.old_snapshot;
+                    self.changes = Some(result.changes);
+                    self.dependencies = Some(result.dependencies);
+                    self.snapshot = Some(result.snapshot);
+                    self.scan_done = true;
+                    self.scanning = false;
+                    self.scan_rx = None;
+                    self.tab = Tab::Results;
+
+                    if let Some(dir) = self.profile_dir() {
+                        self.history = find_snapshot_history(&dir);
+                    }
                 }
+                // The worker died without a result (a panic outside the
+                // per-jar guard); stop waiting for it.
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.scanning = false;
+                    self.scan_rx = None;
+                    self.status = T::scan_crashed(l).to_string();
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
             }
         }
 
@@ -645,6 +778,9 @@ impl eframe::App for App {
                 } else if !self.status.is_empty() {
                     ui.label(&self.status);
                 }
+                for err in &self.profile_errors {
+                    ui.colored_label(egui::Color32::RED, err);
+                }
             });
         });
 
@@ -676,7 +812,7 @@ impl App {
                 });
         });
         if self.lang != old_lang {
-            save_language(self.lang);
+            self.save_settings();
             ui.ctx().send_viewport_cmd(egui::ViewportCommand::Title(
                 T::window_title(self.lang).to_string(),
             ));
@@ -779,6 +915,20 @@ impl App {
 
         ui.add_space(8.0);
         ui.checkbox(&mut self.force_new, T::force_new(l));
+        ui.checkbox(&mut self.markdown_options.group_updates, T::group_updates(l));
+
+        ui.add_space(8.0);
+        ui.label(T::normalization_label(l));
+        let n = &mut self.compare_options.normalization;
+        let changed = ui.checkbox(&mut n.build_metadata, T::ignore_build_metadata(l)).changed()
+            | ui.checkbox(&mut n.mc_version, T::ignore_mc_version(l)).changed()
+            | ui.checkbox(&mut n.loader, T::ignore_loader(l)).changed()
+            | ui
+                .checkbox(&mut self.compare_options.list_suffix_only, T::list_suffix_only(l))
+                .changed();
+        if changed {
+            self.save_settings();
+        }
 
         ui.add_space(16.0);
         ui.separator();
@@ -830,6 +980,8 @@ impl App {
         let base_name = self.base_name.clone();
         let pack_version = self.pack_version.clone();
         let force_new = self.force_new;
+        let compare_options = self.load_profile_rules();
+        let markdown_options = self.markdown_options.clone();
         let lang = self.lang;
 
         // Pick the comparison baseline (newest existing snapshot of this
@@ -857,35 +1009,70 @@ impl App {
             let snapshot_dir = mods_path.parent().unwrap_or(&mods_path).to_path_buf();
             let snapshot_path = snapshot_dir.join(format!("{}.mods_snapshot.json", prefix));
             let md_path = snapshot_dir.join(format!("{}.changelog.md", prefix));
+            let server_md_path = snapshot_dir.join(format!("{}.server-changelog.md", prefix));
+            let internal_md_path = snapshot_dir.join(format!("{}.internal-changelog.md", prefix));
 
-            let new_snapshot = scan_mods_directory(&mods_path);
+            let cache_path = snapshot_dir.join(SCAN_CACHE_FILE);
+            let mut cache = ScanCache::load(&cache_path);
+            let new_snapshot = scan_mods_directory(&mods_path, &mut cache);
+            cache.save(&cache_path);
 
             let old_snapshot = baseline;
 
             let changes = if let Some(ref old) = old_snapshot {
-                compare_snapshots(old, &new_snapshot)
+                compare_snapshots(old, &new_snapshot, &compare_options)
             } else {
-                Changes {
+                let mut changes = Changes {
                     added: new_snapshot.active.clone(),
+                    currently_disabled: new_snapshot.disabled.clone(),
                     ..Changes::default()
-                }
+                };
+                compare_options.filter.apply(&mut changes);
+                changes
             };
 
+            let dependencies = check_dependencies(&new_snapshot);
+
             let markdown = generate_markdown(
                 &display_name,
                 &changes,
                 &new_snapshot,
                 old_snapshot.as_ref(),
                 lang,
+                &markdown_options,
             );
+            let server_markdown = generate_server_markdown(
+                &display_name,
+                &changes,
+                &new_snapshot,
+                old_snapshot.as_ref(),
+                lang,
+            );
+            let internal_markdown = if changes.internal.is_some() {
+                generate_markdown(
+                    &display_name,
+                    &changes.with_internal(),
+                    &new_snapshot,
+                    old_snapshot.as_ref(),
+                    lang,
+                    &markdown_options,
+                )
+            } else {
+                String::new()
+            };
 
             let _ = tx.send(ScanResult {
                 snapshot: new_snapshot,
                 old_snapshot,
                 changes,
+                dependencies,
                 markdown,
+                server_markdown,
+                internal_markdown,
                 snapshot_path,
                 md_path,
+                server_md_path,
+                internal_md_path,
             });
         });
     }
@@ -923,6 +1110,48 @@ impl App {
                 ui.end_row();
             });
 
+        if !snapshot.duplicates.is_empty() {
+            ui.add_space(12.0);
+            ui.separator();
+            ui.add_space(8.0);
+            Self::show_duplicate_ids(ui, &snapshot.duplicates, l);
+        }
+
+        if let Some(report) = &self.dependencies {
+            ui.add_space(12.0);
+            ui.separator();
+            ui.add_space(8.0);
+            Self::show_dependency_report(ui, report, l);
+        }
+
+        let doubtful = low_confidence_mods(snapshot);
+        if !doubtful.is_empty() {
+            ui.add_space(12.0);
+            ui.separator();
+            ui.add_space(8.0);
+            ui.collapsing(T::check_these(l, doubtful.len()), |ui| {
+                egui::ScrollArea::vertical()
+                    .id_salt("check_these")
+                    .max_height(160.0)
+                    .show(ui, |ui| {
+                        for m in doubtful {
+                            let doubts = m.doubts(l).join(", ");
+                            ui.colored_label(
+                                egui::Color32::YELLOW,
+                                T::check_these_detail(l, &m.name, &m.filename, &doubts),
+                            );
+                        }
+                    });
+            });
+        }
+
+        if !snapshot.failed.is_empty() {
+            ui.add_space(12.0);
+            ui.separator();
+            ui.add_space(8.0);
+            Self::show_read_errors(ui, &snapshot.failed, l);
+        }
+
         ui.add_space(12.0);
         ui.separator();
         ui.add_space(8.0);
@@ -930,58 +1159,116 @@ impl App {
         ui.heading(T::changes_heading(l));
         ui.add_space(4.0);
 
-        Self::show_changes_list(ui, changes, self.old_snapshot.is_some(), l);
-    }
-
-    fn show_changes_list(ui: &mut egui::Ui, changes: &Changes, has_old: bool, l: Lang) {
-        egui::ScrollArea::vertical().show(ui, |ui| {
-            if !changes.added.is_empty() {
-                ui.collapsing(T::added(l, changes.added.len()), |ui| {
-                    let mut sorted = changes.added.clone();
-                    sorted.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-                    for m in &sorted {
-                        ui.label(format!("  {} v{}", m.name, m.version));
-                    }
+        if !self.markdown_options.categories.is_empty() {
+            let uncategorized = self.markdown_options.categories.uncategorized(&snapshot.active);
+            if !uncategorized.is_empty() {
+                ui.collapsing(T::uncategorized_mods(l, uncategorized.len()), |ui| {
+                    ui.label(T::uncategorized_hint(l, CATEGORY_FILE));
+                    egui::ScrollArea::vertical()
+                        .id_salt("uncategorized")
+                        .max_height(160.0)
+                        .show(ui, |ui| {
+                            for m in uncategorized {
+                                ui.label(T::uncategorized_detail(l, &m.name, &m.id));
+                            }
+                        });
                 });
+                ui.add_space(4.0);
             }
+        }
 
-            if !changes.updated.is_empty() {
-                ui.collapsing(T::updated(l, changes.updated.len()), |ui| {
-                    let mut sorted = changes.updated.clone();
-                    sorted.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-                    for m in &sorted {
-                        ui.label(T::updated_detail(l, &m.name, &m.new_version, &m.old_version));
-                    }
-                });
-            }
+        let categories = &self.markdown_options.categories;
+        Self::show_changes_list(ui, changes, categories, self.old_snapshot.is_some(), l);
+    }
 
-            if !changes.removed.is_empty() {
-                ui.collapsing(T::removed(l, changes.removed.len()), |ui| {
-                    let mut sorted = changes.removed.clone();
-                    sorted.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-                    for m in &sorted {
-                        ui.label(format!("  {} v{}", m.name, m.version));
-                    }
-                });
-            }
+    fn show_dependency_report(ui: &mut egui::Ui, report: &DependencyReport, l: Lang) {
+        ui.heading(T::dependency_check(l));
+        ui.add_space(4.0);
 
-            if !changes.newly_disabled.is_empty() {
-                ui.collapsing(T::newly_disabled(l, changes.newly_disabled.len()), |ui| {
-                    let mut sorted = changes.newly_disabled.clone();
-                    sorted.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-                    for m in &sorted {
-                        ui.label(format!("  {} v{}", m.name, m.version));
-                    }
+        if report.is_empty() {
+            ui.colored_label(egui::Color32::GREEN, T::dependencies_ok(l));
+            return;
+        }
+
+        egui::ScrollArea::vertical()
+            .id_salt("dependency_report")
+            .max_height(160.0)
+            .show(ui, |ui| {
+                for d in &report.missing {
+                    let text = if d.disabled {
+                        T::dependency_disabled(l, &d.mod_name, &d.dep_id, &d.versions)
+                    } else {
+                        T::dependency_missing(l, &d.mod_name, &d.dep_id, &d.versions)
+                    };
+                    ui.colored_label(egui::Color32::RED, text);
+                }
+                for b in &report.breaking {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        T::dependency_breaks(l, &b.mod_name, &b.other_name, &b.other_version, &b.versions),
+                    );
+                }
+            });
+    }
+
+    fn show_duplicate_ids(ui: &mut egui::Ui, duplicates: &[DuplicateId], l: Lang) {
+        ui.heading(egui::RichText::new(T::duplicate_ids(l, duplicates.len())).color(egui::Color32::RED));
+        ui.label(T::duplicate_ids_hint(l));
+        ui.add_space(4.0);
+
+        egui::ScrollArea::vertical()
+            .id_salt("duplicate_ids")
+            .max_height(160.0)
+            .show(ui, |ui| {
+                for d in duplicates {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        T::duplicate_id_detail(l, &d.id, &d.filenames.join(", ")),
+                    );
+                }
+            });
+    }
+
+    fn show_read_errors(ui: &mut egui::Ui, failed: &[ScanFailure], l: Lang) {
+        ui.heading(T::read_errors_heading(l));
+        ui.add_space(4.0);
+
+        egui::ScrollArea::vertical()
+            .id_salt("read_errors")
+            .max_height(160.0)
+            .show(ui, |ui| {
+                for (reason, files) in group_failures(failed) {
+                    ui.collapsing(T::read_error_group(l, reason.label(l), files.len()), |ui| {
+                        for f in files {
+                            match &f.detail {
+                                Some(detail) => ui.label(format!("  {} — {}", f.filename, detail)),
+                                None => ui.label(format!("  {}", f.filename)),
+                            };
+                        }
+                    });
+                }
+            });
+    }
+
+    fn show_changes_list(
+        ui: &mut egui::Ui,
+        changes: &Changes,
+        categories: &CategoryMap,
+        has_old: bool,
+        l: Lang,
+    ) {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            Self::show_change_sections(ui, changes, categories, l);
+
+            if let Some(libraries) = &changes.libraries {
+                ui.collapsing(T::libraries(l, libraries.total_changes()), |ui| {
+                    Self::show_change_sections(ui, libraries, categories, l);
                 });
             }
 
-            if !changes.newly_enabled.is_empty() {
-                ui.collapsing(T::newly_enabled(l, changes.newly_enabled.len()), |ui| {
-                    let mut sorted = changes.newly_enabled.clone();
-                    sorted.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-                    for m in &sorted {
-                        ui.label(format!("  {} v{}", m.name, m.version));
-                    }
+            if let Some(internal) = &changes.internal {
+                ui.collapsing(T::internal_only(l, internal.total_changes()), |ui| {
+                    Self::show_change_sections(ui, internal, categories, l);
                 });
             }
 
@@ -994,6 +1281,205 @@ impl App {
         });
     }
 
+    /// One collapsing section per kind of change; the filter's library and
+    /// internal buckets are rendered with the same sections, one level down.
+    fn show_change_sections(
+        ui: &mut egui::Ui,
+        changes: &Changes,
+        categories: &CategoryMap,
+        l: Lang,
+    ) {
+        if !changes.added.is_empty() {
+            ui.collapsing(T::added(l, changes.added.len()), |ui| {
+                let mut sorted = changes.added.clone();
+                sorted.sort_by_key(|a| a.name.to_lowercase());
+                for m in &sorted {
+                    Self::mod_row(ui, categories, &m.id, format!("  {} v{}", m.name, m.version));
+                }
+            });
+        }
+
+        let (mut downgraded, mut upgraded): (Vec<_>, Vec<_>) = changes
+            .updated
+            .iter()
+            .partition(|m| m.kind == UpdateKind::Downgrade);
+
+        if !upgraded.is_empty() {
+            ui.collapsing(T::updated(l, upgraded.len()), |ui| {
+                upgraded.sort_by_key(|a| a.name.to_lowercase());
+                for m in &upgraded {
+                    let text = T::updated_detail(l, &m.name, &m.new_version, &m.old_version);
+                    Self::mod_row(ui, categories, &m.id, text);
+                }
+            });
+        }
+
+        if !downgraded.is_empty() {
+            ui.collapsing(T::downgraded(l, downgraded.len()), |ui| {
+                downgraded.sort_by_key(|a| a.name.to_lowercase());
+                for m in &downgraded {
+                    let text = T::updated_detail(l, &m.name, &m.new_version, &m.old_version);
+                    let text = egui::RichText::new(text).color(egui::Color32::YELLOW);
+                    Self::mod_row(ui, categories, &m.id, text);
+                }
+            });
+        }
+
+        if !changes.suffix_only.is_empty() {
+            ui.collapsing(T::suffix_only(l, changes.suffix_only.len()), |ui| {
+                let mut sorted = changes.suffix_only.clone();
+                sorted.sort_by_key(|a| a.name.to_lowercase());
+                for m in &sorted {
+                    let text = T::updated_detail(l, &m.name, &m.new_version, &m.old_version);
+                    Self::mod_row(ui, categories, &m.id, text);
+                }
+            });
+        }
+
+        if !changes.rebuilt.is_empty() {
+            ui.collapsing(T::rebuilt(l, changes.rebuilt.len()), |ui| {
+                let mut sorted = changes.rebuilt.clone();
+                sorted.sort_by_key(|a| a.name.to_lowercase());
+                for m in &sorted {
+                    Self::mod_row(ui, categories, &m.id, format!("  {} v{}", m.name, m.version));
+                }
+            });
+        }
+
+        if !changes.replaced.is_empty() {
+            ui.collapsing(T::replaced(l, changes.replaced.len()), |ui| {
+                let mut sorted = changes.replaced.clone();
+                sorted.sort_by_key(|r| r.old.name.to_lowercase());
+                for r in &sorted {
+                    let text = T::replaced_detail(
+                        l,
+                        &r.old.name,
+                        &r.old.version,
+                        &r.new.name,
+                        &r.new.version,
+                        r.matched_by.label(l),
+                    );
+                    Self::mod_row(ui, categories, &r.new.id, text);
+                }
+            });
+        }
+
+        let platform = changes.loader_changed.len() + changes.environment_changed.len();
+        if platform > 0 {
+            ui.collapsing(T::platform_changed(l, platform), |ui| {
+                let mut entries: Vec<(String, String, &str)> = changes
+                    .loader_changed
+                    .iter()
+                    .map(|c| {
+                        let line = T::loader_changed_detail(l, &c.name, &c.old_loader, &c.new_loader);
+                        (c.name.to_lowercase(), line, c.id.as_str())
+                    })
+                    .chain(changes.environment_changed.iter().map(|c| {
+                        let line = T::environment_changed_detail(
+                            l,
+                            &c.name,
+                            c.old_environment.label(l),
+                            c.new_environment.label(l),
+                        );
+                        (c.name.to_lowercase(), line, c.id.as_str())
+                    }))
+                    .collect();
+                entries.sort();
+                for (_, line, id) in entries {
+                    Self::mod_row(ui, categories, id, line);
+                }
+            });
+        }
+
+        if !changes.nested_updated.is_empty() {
+            ui.collapsing(T::nested_updated(l, changes.nested_updated.len()), |ui| {
+                let mut sorted = changes.nested_updated.clone();
+                sorted.sort_by_key(|a| (a.host_name.to_lowercase(), a.name.to_lowercase()));
+                for m in &sorted {
+                    let text = T::nested_updated_detail(
+                        l,
+                        &m.host_name,
+                        &m.name,
+                        &m.new_version,
+                        &m.old_version,
+                    );
+                    Self::mod_row(ui, categories, &m.host_id, text);
+                }
+            });
+        }
+
+        if !changes.removed.is_empty() {
+            ui.collapsing(T::removed(l, changes.removed.len()), |ui| {
+                let mut sorted = changes.removed.clone();
+                sorted.sort_by_key(|a| a.name.to_lowercase());
+                for m in &sorted {
+                    Self::mod_row(ui, categories, &m.id, format!("  {} v{}", m.name, m.version));
+                }
+            });
+        }
+
+        if !changes.newly_disabled.is_empty() {
+            ui.collapsing(T::newly_disabled(l, changes.newly_disabled.len()), |ui| {
+                let mut sorted = changes.newly_disabled.clone();
+                sorted.sort_by_key(|t| t.new.name.to_lowercase());
+                for t in &sorted {
+                    let text = if t.version_changed {
+                        T::disabled_detail(l, &t.new.name, &t.new.version, &t.old.version)
+                    } else {
+                        format!("  {} v{}", t.new.name, t.new.version)
+                    };
+                    Self::mod_row(ui, categories, &t.new.id, text);
+                }
+            });
+        }
+
+        if !changes.newly_enabled.is_empty() {
+            ui.collapsing(T::newly_enabled(l, changes.newly_enabled.len()), |ui| {
+                let mut sorted = changes.newly_enabled.clone();
+                sorted.sort_by_key(|t| t.new.name.to_lowercase());
+                for t in &sorted {
+                    let text = if t.version_changed {
+                        T::reenabled_detail(l, &t.new.name, &t.new.version, &t.old.version)
+                    } else {
+                        format!("  {} v{}", t.new.name, t.new.version)
+                    };
+                    Self::mod_row(ui, categories, &t.new.id, text);
+                }
+            });
+        }
+
+        if !changes.disabled_updated.is_empty() {
+            ui.collapsing(T::disabled_updated(l, changes.disabled_updated.len()), |ui| {
+                let mut sorted = changes.disabled_updated.clone();
+                sorted.sort_by_key(|a| a.name.to_lowercase());
+                for m in &sorted {
+                    let text = T::updated_detail(l, &m.name, &m.new_version, &m.old_version);
+                    Self::mod_row(ui, categories, &m.id, text);
+                }
+            });
+        }
+    }
+
+    /// A change row, followed by the mod's category badge if it has one.
+    fn mod_row(
+        ui: &mut egui::Ui,
+        categories: &CategoryMap,
+        id: &str,
+        text: impl Into<egui::WidgetText>,
+    ) {
+        ui.horizontal(|ui| {
+            ui.label(text);
+            if let Some(category) = categories.category(id) {
+                ui.label(
+                    egui::RichText::new(category)
+                        .small()
+                        .color(egui::Color32::BLACK)
+                        .background_color(egui::Color32::LIGHT_BLUE),
+                );
+            }
+        });
+    }
+
     fn show_markdown(&mut self, ui: &mut egui::Ui) {
         let l = self.l();
 
@@ -1006,8 +1492,27 @@ impl App {
         ui.heading(T::generated_markdown(l));
         ui.add_space(8.0);
 
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.markdown_view, MarkdownView::Full, T::full_changelog(l));
+            ui.selectable_value(&mut self.markdown_view, MarkdownView::Server, T::server_changelog(l));
+            if !self.internal_markdown.is_empty() {
+                ui.selectable_value(
+                    &mut self.markdown_view,
+                    MarkdownView::Internal,
+                    T::internal_changelog(l),
+                );
+            }
+        });
+        let markdown = match self.markdown_view {
+            MarkdownView::Full => &self.markdown,
+            MarkdownView::Server => &self.server_markdown,
+            MarkdownView::Internal => &self.internal_markdown,
+        };
+
+        ui.add_space(8.0);
+
         if ui.button(T::copy_to_clipboard(l)).clicked() {
-            ui.ctx().copy_text(self.markdown.clone());
+            ui.ctx().copy_text(markdown.clone());
             self.status = T::copied(l).to_string();
         }
 
@@ -1015,7 +1520,7 @@ impl App {
 
         egui::ScrollArea::vertical().show(ui, |ui| {
             ui.add(
-                egui::TextEdit::multiline(&mut self.markdown.as_str())
+                egui::TextEdit::multiline(&mut markdown.as_str())
                     .desired_width(f32::INFINITY)
                     .font(egui::TextStyle::Monospace),
             );
@@ -1044,6 +1549,18 @@ impl App {
                     self.compare_indices(1, 0);
                 }
             });
+            let both_editions = EDITIONS
+                .iter()
+                .all(|e| latest_snapshot_for_edition(&self.history, e).is_some());
+            ui.add_enabled_ui(both_editions, |ui| {
+                if ui
+                    .button(T::compare_editions(l, EDITIONS[0], EDITIONS[1]))
+                    .on_hover_text(T::compare_editions_hint(l))
+                    .clicked()
+                {
+                    self.compare_latest_editions();
+                }
+            });
         });
 
         ui.add_space(8.0);
@@ -1115,12 +1632,29 @@ impl App {
             && self.history_selected_a != self.history_selected_b;
 
         ui.add_enabled_ui(can_compare, |ui| {
-            if ui.button(T::compare_selected(l)).clicked() {
-                if let (Some(a), Some(b)) = (self.history_selected_a, self.history_selected_b) {
-                    self.compare_indices(a, b);
+            ui.horizontal(|ui| {
+                if ui.button(T::compare_selected(l)).clicked() {
+                    if let (Some(a), Some(b)) = (self.history_selected_a, self.history_selected_b) {
+                        self.compare_indices(a, b);
+                    }
                 }
-            }
+                if ui
+                    .button(T::compare_range(l))
+                    .on_hover_text(T::compare_range_hint(l))
+                    .clicked()
+                {
+                    if let (Some(a), Some(b)) = (self.history_selected_a, self.history_selected_b) {
+                        self.compare_range_indices(a, b);
+                    }
+                }
+            });
         });
+        let per_step = ui.checkbox(&mut self.markdown_options.per_step, T::per_step_breakdown(l));
+        if per_step.changed() && !self.history_steps.is_empty() {
+            if let (Some(a), Some(b)) = (self.history_selected_a, self.history_selected_b) {
+                self.compare_range_indices(a, b);
+            }
+        }
 
         if let Some(ref changes) = self.history_changes.clone() {
             ui.add_space(12.0);
@@ -1129,16 +1663,102 @@ impl App {
 
             ui.heading(T::history_comparison(l));
 
-            if !self.history_markdown.is_empty() && ui.button(T::copy_history_md(l)).clicked() {
-                ui.ctx().copy_text(self.history_markdown.clone());
+            if !self.history_markdown.is_empty() {
+                ui.horizontal(|ui| {
+                    if ui.button(T::copy_history_md(l)).clicked() {
+                        ui.ctx().copy_text(self.history_markdown.clone());
+                        self.status = T::history_md_copied(l).to_string();
+                    }
+                    if ui.button(T::copy_history_server_md(l)).clicked() {
+                        ui.ctx().copy_text(self.history_server_markdown.clone());
+                        self.status = T::history_md_copied(l).to_string();
+                    }
+                    if !self.history_internal_markdown.is_empty()
+                        && ui.button(T::copy_history_internal_md(l)).clicked()
+                    {
+                        ui.ctx().copy_text(self.history_internal_markdown.clone());
+                        self.status = T::history_md_copied(l).to_string();
+                    }
+                });
+            }
+
+            ui.add_space(4.0);
+            Self::show_changes_list(ui, changes, &self.markdown_options.categories, true, l);
+
+            if self.markdown_options.per_step && !self.history_steps.is_empty() {
+                ui.add_space(8.0);
+                ui.collapsing(T::range_steps(l, self.history_steps.len()), |ui| {
+                    for (i, step) in self.history_steps.iter().enumerate() {
+                        let title = T::range_step(
+                            l,
+                            i + 1,
+                            &short_timestamp(&step.from),
+                            &short_timestamp(&step.to),
+                            step.changes.total_changes(),
+                        );
+                        ui.collapsing(title, |ui| {
+                            let categories = &self.markdown_options.categories;
+                            Self::show_changes_list(ui, &step.changes, categories, true, l);
+                        });
+                    }
+                });
+            }
+        }
+
+        if let Some(diff) = &self.edition_diff {
+            ui.add_space(12.0);
+            ui.separator();
+            ui.add_space(8.0);
+
+            ui.heading(T::edition_comparison(l));
+            if ui.button(T::copy_history_md(l)).clicked() {
+                ui.ctx().copy_text(self.edition_markdown.clone());
                 self.status = T::history_md_copied(l).to_string();
             }
 
             ui.add_space(4.0);
-            Self::show_changes_list(ui, changes, true, l);
+            Self::show_edition_diff(ui, diff, l);
         }
     }
 
+    fn show_edition_diff(ui: &mut egui::Ui, diff: &EditionDiff, l: Lang) {
+        egui::ScrollArea::vertical().id_salt("edition_diff").show(ui, |ui| {
+            for (edition, only) in [(EDITIONS[0], &diff.only_in_a), (EDITIONS[1], &diff.only_in_b)] {
+                if only.is_empty() {
+                    continue;
+                }
+                ui.collapsing(T::edition_only_in(l, edition, only.len()), |ui| {
+                    let mut sorted = only.clone();
+                    sorted.sort_by_key(|a| a.name.to_lowercase());
+                    for m in &sorted {
+                        ui.label(format!("  {} v{}", m.name, m.version));
+                    }
+                });
+            }
+
+            if !diff.version_mismatch.is_empty() {
+                ui.collapsing(T::edition_version_diff(l, diff.version_mismatch.len()), |ui| {
+                    let mut sorted = diff.version_mismatch.clone();
+                    sorted.sort_by_key(|a| a.name.to_lowercase());
+                    for m in &sorted {
+                        ui.label(T::edition_version_detail(
+                            l,
+                            &m.name,
+                            EDITIONS[0],
+                            &m.version_a,
+                            EDITIONS[1],
+                            &m.version_b,
+                        ));
+                    }
+                });
+            }
+
+            if diff.total_differences() == 0 {
+                ui.label(T::edition_same(l));
+            }
+        });
+    }
+
     fn reload_history(&mut self) {
         if let Some(dir) = self.profile_dir() {
             self.history = find_snapshot_history(&dir);
@@ -1147,6 +1767,11 @@ impl App {
         self.history_selected_b = None;
         self.history_changes = None;
         self.history_markdown.clear();
+        self.history_server_markdown.clear();
+        self.history_internal_markdown.clear();
+        self.history_steps.clear();
+        self.edition_diff = None;
+        self.edition_markdown.clear();
     }
 
     /// Adds `i` to the comparison selection. Keeps at most two picks; the
@@ -1204,9 +1829,31 @@ impl App {
         let old = self.history[old_idx].snapshot.clone();
         let new = self.history[new_idx].snapshot.clone();
 
-        let changes = compare_snapshots(&old, &new);
+        let compare_options = self.load_profile_rules();
+        let changes = compare_snapshots(&old, &new, &compare_options);
         let display = build_display_name(&self.base_name, self.edition(), &self.pack_version);
-        let md = generate_markdown(&display, &changes, &new, Some(&old), l);
+        let md = generate_markdown(
+            &display,
+            &changes,
+            &new,
+            Some(&old),
+            l,
+            &self.markdown_options,
+        );
+        let server_md =
+            generate_server_markdown(&display, &changes, &new, Some(&old), l);
+        let internal_md = if changes.internal.is_some() {
+            generate_markdown(
+                &display,
+                &changes.with_internal(),
+                &new,
+                Some(&old),
+                l,
+                &self.markdown_options,
+            )
+        } else {
+            String::new()
+        };
 
         self.status = T::history_summary(
             l,
@@ -1218,6 +1865,105 @@ impl App {
         self.history_selected_a = Some(old_idx);
         self.history_selected_b = Some(new_idx);
         self.history_markdown = md;
+        self.history_server_markdown = server_md;
+        self.history_internal_markdown = internal_md;
         self.history_changes = Some(changes);
+        self.history_steps.clear();
+        self.edition_diff = None;
+        self.edition_markdown.clear();
+    }
+
+    /// Compares the newest snapshot of each edition, regardless of the
+    /// history selection.
+    fn compare_latest_editions(&mut self) {
+        let l = self.l();
+        let compare_options = self.load_profile_rules();
+        let (edition_a, edition_b) = (EDITIONS[0], EDITIONS[1]);
+        let (Some(a), Some(b)) = (
+            latest_snapshot_for_edition(&self.history, edition_a),
+            latest_snapshot_for_edition(&self.history, edition_b),
+        ) else {
+            return;
+        };
+
+        let diff = compare_editions(&a.snapshot, &b.snapshot, &compare_options);
+        // Without a pack version the display name is just the base name.
+        let display = build_display_name(&self.base_name, edition_a, "");
+        let md = generate_edition_markdown(
+            &display,
+            (edition_a, &a.snapshot),
+            (edition_b, &b.snapshot),
+            &diff,
+            l,
+        );
+        self.status = T::edition_summary(l, diff.total_differences(), &a.filename, &b.filename);
+
+        self.history_changes = None;
+        self.history_markdown.clear();
+        self.history_server_markdown.clear();
+        self.history_internal_markdown.clear();
+        self.history_steps.clear();
+        self.edition_markdown = md;
+        self.edition_diff = Some(diff);
+    }
+
+    /// Like [`Self::compare_indices`], but walks every snapshot between the
+    /// two picks. Snapshots of other editions in between are skipped, so a
+    /// Full range isn't interleaved with Lite scans.
+    fn compare_range_indices(&mut self, i: usize, j: usize) {
+        let l = self.l();
+        if i >= self.history.len() || j >= self.history.len() || i == j {
+            return;
+        }
+
+        // history is newest-first, so the larger index is the older snapshot.
+        let (old_idx, new_idx) = if i > j { (i, j) } else { (j, i) };
+        let edition = self.history[new_idx].edition.clone();
+        let compare_options = self.load_profile_rules();
+        let snapshots: Vec<&Snapshot> = (new_idx..=old_idx)
+            .rev()
+            .filter(|&k| k == old_idx || k == new_idx || self.history[k].edition == edition)
+            .map(|k| &self.history[k].snapshot)
+            .collect();
+
+        let range = compare_range(&snapshots, &compare_options);
+        let display = build_display_name(&self.base_name, self.edition(), &self.pack_version);
+        let md = generate_range_markdown(&display, &range, &snapshots, l, &self.markdown_options);
+        let server_md = generate_server_markdown(
+            &display,
+            &range.total,
+            snapshots[snapshots.len() - 1],
+            Some(snapshots[0]),
+            l,
+        );
+        let internal_md = if range.has_internal() {
+            generate_range_markdown(
+                &display,
+                &range.with_internal(),
+                &snapshots,
+                l,
+                &self.markdown_options,
+            )
+        } else {
+            String::new()
+        };
+
+        self.status = T::history_range_summary(
+            l,
+            range.total.total_changes(),
+            range.steps.len(),
+            &self.history[old_idx].filename,
+            &self.history[new_idx].filename,
+        );
+
+        self.history_selected_a = Some(old_idx);
+        self.history_selected_b = Some(new_idx);
+        self.history_markdown = md;
+        self.history_server_markdown = server_md;
+        self.history_internal_markdown = internal_md;
+        self.history_changes = Some(range.total);
+        self.history_steps = range.steps;
+        self.edition_diff = None;
+        self.edition_markdown.clear();
     }
 }
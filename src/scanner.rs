@@ -1,9 +1,15 @@
+use crate::category::CategoryMap;
+use crate::filter::{FilterAction, ModFilter};
 use crate::lang::{Lang, T};
+use crate::lenient_json::{self, Repair};
+use crate::version::{classify_update, matches_range, normalize_version, Normalization, UpdateKind};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::io::Read;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::{Cursor, Read, Seek};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::LazyLock;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModData {
@@ -13,6 +19,176 @@ pub struct ModData {
     pub version: String,
     pub loader: String,
     pub disabled: bool,
+    /// Mod ids this mod also provides (Fabric `provides`, Quilt `quilt_loader.provides`).
+    #[serde(default)]
+    pub provides: Vec<String>,
+    /// Declared dependency relations, each with its version range. Forge's
+    /// required/optional/incompatible/discouraged map onto depends/suggests/
+    /// breaks/conflicts.
+    #[serde(default)]
+    pub depends: Vec<Dependency>,
+    #[serde(default)]
+    pub recommends: Vec<Dependency>,
+    #[serde(default)]
+    pub suggests: Vec<Dependency>,
+    #[serde(default)]
+    pub breaks: Vec<Dependency>,
+    #[serde(default)]
+    pub conflicts: Vec<Dependency>,
+    /// Mods bundled inside this jar (Fabric/Quilt `jars`, Forge Jar-in-Jar).
+    /// Their `filename` is the path inside the host archive.
+    #[serde(default)]
+    pub nested: Vec<ModData>,
+    /// Jar size in bytes and its SHA-1 / SHA-512 (hex, as used by Modrinth).
+    /// Empty for nested mods and for snapshots taken before hashing existed.
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default)]
+    pub sha1: String,
+    #[serde(default)]
+    pub sha512: String,
+    /// Credits and links from fabric.mod.json / quilt.mod.json. Quilt
+    /// contributors with an Owner or Author role count as authors. `license`
+    /// holds SPDX ids (or names), `icon` the path of the largest icon inside
+    /// the jar. Empty when the metadata doesn't say, or for older snapshots.
+    #[serde(default)]
+    pub authors: Vec<String>,
+    #[serde(default)]
+    pub contributors: Vec<String>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub license: Vec<String>,
+    #[serde(default)]
+    pub homepage: String,
+    #[serde(default)]
+    pub sources: String,
+    #[serde(default)]
+    pub issues: String,
+    #[serde(default)]
+    pub icon: String,
+    /// Fixes the lenient JSON reader had to apply to this mod's metadata
+    /// file. Empty for well-formed files and for TOML metadata.
+    #[serde(default)]
+    pub repairs: Vec<Repair>,
+    /// Where `version` came from. Metadata versions that are unexpanded
+    /// build placeholders (`${version}`, `$version`, `@VERSION@`) are
+    /// replaced from the manifest or the filename.
+    #[serde(default)]
+    pub version_source: VersionSource,
+    /// How the id, name and version were obtained. Together with
+    /// `version_source` this decides whether the entry can be trusted.
+    #[serde(default)]
+    pub provenance: Provenance,
+    #[serde(default)]
+    pub environment: Environment,
+}
+
+impl ModData {
+    /// Why this entry's data may be wrong, as short translated phrases.
+    /// Empty for mods read from well-formed (or cleanly repaired) metadata.
+    pub fn doubts(&self, lang: Lang) -> Vec<&'static str> {
+        let mut doubts = Vec::new();
+        match self.provenance {
+            Provenance::Parsed | Provenance::Repaired => {}
+            Provenance::Regex => doubts.push(T::doubt_regex(lang)),
+            Provenance::FileStem => doubts.push(T::doubt_file_stem(lang)),
+        }
+        match self.version_source {
+            VersionSource::Metadata | VersionSource::Manifest => {}
+            VersionSource::Filename => doubts.push(T::doubt_version_filename(lang)),
+            VersionSource::Unknown => doubts.push(T::doubt_version_unknown(lang)),
+        }
+        doubts
+    }
+
+    pub fn is_low_confidence(&self) -> bool {
+        matches!(self.provenance, Provenance::Regex | Provenance::FileStem)
+            || matches!(self.version_source, VersionSource::Filename | VersionSource::Unknown)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Provenance {
+    /// Strict JSON or TOML parse of the metadata file.
+    #[default]
+    Parsed,
+    /// Parsed after the lenient JSON reader fixed the file (see `repairs`).
+    Repaired,
+    /// The JSON was beyond repair; id, name and version were scraped with
+    /// regexes and everything else is missing.
+    Regex,
+    /// The metadata had no mod id, so the jar's file stem stands in for it.
+    FileStem,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionSource {
+    /// The mod's own metadata file.
+    #[default]
+    Metadata,
+    /// `Implementation-Version` in `META-INF/MANIFEST.MF`.
+    Manifest,
+    /// Parsed from the jar filename.
+    Filename,
+    /// Nothing usable was found; the version is "unknown".
+    Unknown,
+}
+
+/// Which side of the game a mod runs on (Fabric `environment`, Quilt
+/// `minecraft.environment`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Environment {
+    /// Not declared by the metadata format (Forge, `mcmod.info`), or a
+    /// snapshot from before environments were recorded.
+    #[default]
+    Unknown,
+    /// `*`: client and dedicated server.
+    Both,
+    Client,
+    Server,
+}
+
+impl Environment {
+    /// Read an `environment` value. A missing field means `*`, as in the
+    /// Fabric spec; values this reader doesn't know stay unknown.
+    fn from_json(val: Option<&serde_json::Value>) -> Self {
+        match val.map(|v| v.as_str()) {
+            None | Some(Some("*")) => Environment::Both,
+            Some(Some("client")) => Environment::Client,
+            Some(Some("server" | "dedicated_server")) => Environment::Server,
+            Some(_) => Environment::Unknown,
+        }
+    }
+
+    /// Whether the mod may be needed on a dedicated server. Unknown counts,
+    /// since nothing rules it out.
+    pub fn runs_on_server(self) -> bool {
+        self != Environment::Client
+    }
+
+    pub fn label(self, lang: Lang) -> &'static str {
+        match self {
+            Environment::Unknown => T::env_unknown(lang),
+            Environment::Both => T::env_both(lang),
+            Environment::Client => T::env_client(lang),
+            Environment::Server => T::env_server(lang),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dependency {
+    pub id: String,
+    /// Version range as written in the metadata ("*" when unconstrained).
+    pub versions: String,
+    /// Quilt `optional` dependencies and members of "any of" groups, which
+    /// don't need to be present for the mod to load.
+    #[serde(default)]
+    pub optional: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +197,9 @@ pub struct SnapshotStats {
     pub active: usize,
     pub disabled: usize,
     pub failed: usize,
+    /// Jars whose metadata was taken from the scan cache instead of being parsed.
+    #[serde(default)]
+    pub cached: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,78 +208,187 @@ pub struct Snapshot {
     pub mods_dir: String,
     pub active: Vec<ModData>,
     pub disabled: Vec<ModData>,
-    pub failed: Vec<String>,
+    pub failed: Vec<ScanFailure>,
+    /// Ids shared by more than one active jar. The game refuses to start
+    /// with these, so they are reported as a blocking error.
+    #[serde(default)]
+    pub duplicates: Vec<DuplicateId>,
     pub stats: SnapshotStats,
 }
 
-/// Sanitize JSON text: strip BOM, control chars, comments, trailing commas.
-fn sanitize_json(txt: &str) -> String {
-    let txt = txt.trim_start_matches('\u{feff}');
+/// A mod id that more than one active jar declares.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateId {
+    pub id: String,
+    /// Every jar declaring the id, sorted.
+    pub filenames: Vec<String>,
+}
 
-    // Strip control chars except \t \n \r
-    let control = Regex::new(r"[\x00-\x08\x0B\x0C\x0E-\x1F\x7F]").unwrap();
-    let txt = control.replace_all(txt, "");
-
-    // Strip line comments
-    let line_comments = Regex::new(r"//[^\n\r]*").unwrap();
-    let txt = line_comments.replace_all(&txt, "");
-
-    // Strip block comments
-    let block_comments = Regex::new(r"(?s)/\*.*?\*/").unwrap();
-    let txt = block_comments.replace_all(&txt, "");
-
-    // Strip trailing commas
-    let trailing = Regex::new(r",\s*([}\]])").unwrap();
-    let txt = trailing.replace_all(&txt, "$1");
-
-    // Escape raw newlines inside strings
-    escape_raw_newlines(&txt)
-}
-
-fn escape_raw_newlines(s: &str) -> String {
-    let mut out = String::with_capacity(s.len());
-    let mut inside = false;
-    let mut esc = false;
-
-    for ch in s.chars() {
-        if inside {
-            if esc {
-                out.push(ch);
-                esc = false;
-            } else if ch == '\\' {
-                out.push(ch);
-                esc = true;
-            } else if ch == '"' {
-                out.push(ch);
-                inside = false;
-            } else if ch == '\n' {
-                out.push_str("\\n");
-            } else if ch == '\r' {
-                // skip
-            } else {
-                out.push(ch);
-            }
-        } else {
-            if ch == '"' {
-                out.push(ch);
-                inside = true;
-                esc = false;
-            } else {
-                out.push(ch);
-            }
+/// Why a jar could not be turned into mod entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureReason {
+    /// The file is not a ZIP archive at all.
+    NotZip,
+    /// A ZIP archive that is truncated or damaged.
+    Corrupt,
+    /// The file could not be read from disk.
+    Io,
+    /// A readable archive without any known metadata file.
+    NoMetadata,
+    /// A metadata file is present but could not be parsed.
+    Unparseable,
+    /// Scanning the jar panicked; the other jars are unaffected.
+    Crashed,
+    /// No cause was recorded, as in snapshots from older versions.
+    Unknown,
+}
+
+/// A jar that failed to scan, with the reason and, when available, the
+/// underlying error text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "ScanFailureRepr")]
+pub struct ScanFailure {
+    pub filename: String,
+    pub reason: FailureReason,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Size and hashes as for [`ModData`], so a broken jar can still be
+    /// identified. Empty when the file couldn't be read.
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default)]
+    pub sha1: String,
+    #[serde(default)]
+    pub sha512: String,
+}
+
+impl FailureReason {
+    pub fn label(self, lang: Lang) -> &'static str {
+        match self {
+            FailureReason::NotZip => T::failure_not_zip(lang),
+            FailureReason::Corrupt => T::failure_corrupt(lang),
+            FailureReason::Io => T::failure_io(lang),
+            FailureReason::NoMetadata => T::failure_no_metadata(lang),
+            FailureReason::Unparseable => T::failure_unparseable(lang),
+            FailureReason::Crashed => T::failure_crashed(lang),
+            FailureReason::Unknown => T::failure_unknown(lang),
         }
     }
-    out
 }
 
-/// Regex fallback: grab id, name, version from malformed JSON.
-fn regex_fallback(txt: &str) -> Option<(String, String, String)> {
+/// Failures grouped by reason (in [`FailureReason`] order), each group
+/// sorted by filename.
+pub fn group_failures(failed: &[ScanFailure]) -> Vec<(FailureReason, Vec<&ScanFailure>)> {
+    let mut groups: BTreeMap<FailureReason, Vec<&ScanFailure>> = BTreeMap::new();
+    for f in failed {
+        groups.entry(f.reason).or_default().push(f);
+    }
+    groups
+        .into_iter()
+        .map(|(reason, mut files)| {
+            files.sort_by(|a, b| a.filename.cmp(&b.filename));
+            (reason, files)
+        })
+        .collect()
+}
+
+/// Active and disabled mods whose metadata can't be trusted, sorted by name.
+/// Their diffs may be wrong, so they are worth a manual look.
+pub fn low_confidence_mods(snapshot: &Snapshot) -> Vec<&ModData> {
+    let mut mods: Vec<&ModData> = snapshot
+        .active
+        .iter()
+        .chain(&snapshot.disabled)
+        .filter(|m| m.is_low_confidence())
+        .collect();
+    mods.sort_by_key(|m| m.name.to_lowercase());
+    mods
+}
+
+/// Ids declared by more than one active jar, sorted by id. Several entries
+/// from one jar (e.g. a multi-mod `mods.toml`) count once.
+pub fn find_duplicate_ids(active: &[ModData]) -> Vec<DuplicateId> {
+    let mut by_id: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for m in active {
+        by_id.entry(m.id.as_str()).or_default().insert(m.filename.as_str());
+    }
+    by_id
+        .into_iter()
+        .filter(|(_, files)| files.len() > 1)
+        .map(|(id, files)| DuplicateId {
+            id: id.to_string(),
+            filenames: files.into_iter().map(str::to_string).collect(),
+        })
+        .collect()
+}
+
+/// Old snapshots store failures as bare filenames.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ScanFailureRepr {
+    Legacy(String),
+    Full {
+        filename: String,
+        reason: FailureReason,
+        #[serde(default)]
+        detail: Option<String>,
+        #[serde(default)]
+        size: u64,
+        #[serde(default)]
+        sha1: String,
+        #[serde(default)]
+        sha512: String,
+    },
+}
+
+impl From<ScanFailureRepr> for ScanFailure {
+    fn from(repr: ScanFailureRepr) -> Self {
+        match repr {
+            ScanFailureRepr::Legacy(filename) => ScanFailure {
+                filename,
+                reason: FailureReason::Unknown,
+                detail: None,
+                size: 0,
+                sha1: String::new(),
+                sha512: String::new(),
+            },
+            ScanFailureRepr::Full {
+                filename,
+                reason,
+                detail,
+                size,
+                sha1,
+                sha512,
+            } => ScanFailure {
+                filename,
+                reason,
+                detail,
+                size,
+                sha1,
+                sha512,
+            },
+        }
+    }
+}
+
+/// `"key": "value"` grabbers for [`regex_fallback`], one per key it looks up.
+/// Compiled once and shared by every scan thread.
+static FIELD_RES: LazyLock<HashMap<&'static str, Regex>> = LazyLock::new(|| {
+    ["id", "modid", "name", "version"]
+        .into_iter()
+        .map(|key| (key, Regex::new(&format!(r#""{key}"\s*:\s*"([^"]+)""#)).unwrap()))
+        .collect()
+});
+
+/// Regex fallback: grab id, name, version from malformed JSON. `id_key` is the
+/// key holding the mod id (`id` for Fabric/Quilt, `modid` for mcmod.info).
+pub fn regex_fallback(txt: &str, id_key: &str) -> Option<(String, String, String)> {
     let grab = |key: &str| -> Option<String> {
-        let re = Regex::new(&format!(r#""{key}"\s*:\s*"([^"]+)""#)).ok()?;
-        re.captures(txt).map(|c| c[1].to_string())
+        FIELD_RES.get(key)?.captures(txt).map(|c| c[1].to_string())
     };
 
-    let id = grab("id");
+    let id = grab(id_key);
     let name = grab("name");
     let version = grab("version");
 
@@ -115,67 +403,1101 @@ fn regex_fallback(txt: &str) -> Option<(String, String, String)> {
     }
 }
 
-/// Read mod metadata from a JAR file (fabric.mod.json or quilt.mod.json).
-fn read_meta_from_jar(jar_path: &Path) -> Option<(String, String, String, String)> {
-    let file = std::fs::File::open(jar_path).ok()?;
-    let mut archive = zip::ZipArchive::new(file).ok()?;
+/// Mod metadata as read from a JAR, before filename fallbacks are applied.
+#[derive(Default)]
+struct JarMod {
+    id: String,
+    name: String,
+    version: String,
+    loader: String,
+    provides: Vec<String>,
+    depends: Vec<Dependency>,
+    recommends: Vec<Dependency>,
+    suggests: Vec<Dependency>,
+    breaks: Vec<Dependency>,
+    conflicts: Vec<Dependency>,
+    /// Paths of bundled jars declared by Fabric/Quilt metadata.
+    jars: Vec<String>,
+    nested: Vec<NestedJar>,
+    authors: Vec<String>,
+    contributors: Vec<String>,
+    description: String,
+    license: Vec<String>,
+    homepage: String,
+    sources: String,
+    issues: String,
+    icon: String,
+    repairs: Vec<Repair>,
+    version_source: VersionSource,
+    provenance: Provenance,
+    environment: Environment,
+}
 
-    for candidate in &["fabric.mod.json", "quilt.mod.json"] {
-        let mut entry = match archive.by_name(candidate) {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
+/// A jar bundled inside another one, with the mods read from it.
+struct NestedJar {
+    path: String,
+    mods: Vec<JarMod>,
+}
 
-        let mut raw = Vec::new();
-        if entry.read_to_end(&mut raw).is_err() {
+/// How deep to follow jars nested inside jars.
+const MAX_NESTING_DEPTH: usize = 3;
+
+/// Why [`read_meta_from_jar`] found no mods, with the error text if any.
+type MetaError = (FailureReason, Option<String>);
+
+/// Read a text entry from the archive, tolerating invalid UTF-8. `Ok(None)`
+/// means the entry doesn't exist; `Err` means it exists but can't be read.
+fn read_entry_text<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Result<Option<String>, String> {
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.to_string()),
+    };
+    let mut raw = Vec::new();
+    entry.read_to_end(&mut raw).map_err(|e| e.to_string())?;
+    Ok(Some(
+        String::from_utf8(raw).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()),
+    ))
+}
+
+/// Look up a main-section attribute in `META-INF/MANIFEST.MF`. Handles the
+/// manifest line continuation rule (a leading space joins onto the previous line).
+fn manifest_attr(manifest: &str, key: &str) -> Option<String> {
+    let mut current: Option<(String, String)> = None;
+    for line in manifest.lines() {
+        if let Some(cont) = line.strip_prefix(' ') {
+            if let Some((_, v)) = current.as_mut() {
+                v.push_str(cont);
+            }
             continue;
         }
+        if let Some((k, v)) = current.take() {
+            if k.eq_ignore_ascii_case(key) {
+                return Some(v.trim().to_string());
+            }
+        }
+        // A blank line ends the main section; per-entry sections follow.
+        if line.trim().is_empty() {
+            return None;
+        }
+        if let Some((k, v)) = line.split_once(':') {
+            current = Some((k.trim().to_string(), v.trim_start().to_string()));
+        }
+    }
+    current
+        .filter(|(k, _)| k.eq_ignore_ascii_case(key))
+        .map(|(_, v)| v.trim().to_string())
+}
+
+fn str_field(val: &serde_json::Value, key: &str) -> String {
+    val.get(key)
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string()
+}
+
+/// Quilt ids may be written as `maven.group:mod_id`; only the mod id matters.
+fn strip_maven_group(id: &str) -> String {
+    id.rsplit(':').next().unwrap_or(id).to_string()
+}
+
+/// Render a Quilt version constraint (string, array = any of, or an object
+/// with `any`/`all`) as a single range string.
+fn quilt_versions(val: Option<&serde_json::Value>) -> String {
+    match val {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .map(|v| quilt_versions(Some(v)))
+            .collect::<Vec<_>>()
+            .join(" || "),
+        Some(serde_json::Value::Object(obj)) => {
+            if let Some(any) = obj.get("any") {
+                quilt_versions(Some(any))
+            } else if let Some(serde_json::Value::Array(all)) = obj.get("all") {
+                all.iter()
+                    .map(|v| quilt_versions(Some(v)))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            } else {
+                "*".to_string()
+            }
+        }
+        _ => "*".to_string(),
+    }
+}
+
+/// Parse a Quilt dependency list. Entries are a bare id, an object with
+/// `id`/`versions`/`optional`, or a nested array meaning "any one of these".
+fn quilt_dependencies(val: Option<&serde_json::Value>) -> Vec<Dependency> {
+    fn push_entry(entry: &serde_json::Value, any_of: bool, out: &mut Vec<Dependency>) {
+        match entry {
+            serde_json::Value::String(id) => out.push(Dependency {
+                id: strip_maven_group(id),
+                versions: "*".to_string(),
+                optional: any_of,
+            }),
+            serde_json::Value::Object(obj) => {
+                let Some(id) = obj.get("id").and_then(|v| v.as_str()) else {
+                    return;
+                };
+                let optional = obj.get("optional").and_then(|v| v.as_bool()).unwrap_or(false);
+                out.push(Dependency {
+                    id: strip_maven_group(id),
+                    versions: quilt_versions(obj.get("versions")),
+                    optional: optional || any_of,
+                });
+            }
+            // An "any of" group can't be expressed as a single hard
+            // requirement, so its members are recorded as optional.
+            serde_json::Value::Array(alternatives) => {
+                for alt in alternatives {
+                    push_entry(alt, true, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    if let Some(serde_json::Value::Array(entries)) = val {
+        for entry in entries {
+            push_entry(entry, false, &mut out);
+        }
+    }
+    out
+}
+
+/// Ids listed in a `provides` array. Fabric uses plain strings, Quilt also
+/// allows `{"id": ..., "version": ...}` objects.
+fn provided_ids(val: Option<&serde_json::Value>) -> Vec<String> {
+    let Some(serde_json::Value::Array(items)) = val else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| match item {
+            serde_json::Value::String(id) => Some(strip_maven_group(id)),
+            serde_json::Value::Object(obj) => {
+                obj.get("id").and_then(|v| v.as_str()).map(strip_maven_group)
+            }
+            _ => None,
+        })
+        .collect()
+}
 
-        let txt = String::from_utf8(raw)
-            .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned());
+/// Parse a Fabric dependency block: an object of mod id -> version range,
+/// where the range is a string or an array of alternatives.
+fn fabric_dependencies(val: Option<&serde_json::Value>) -> Vec<Dependency> {
+    let Some(serde_json::Value::Object(entries)) = val else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .map(|(id, range)| Dependency {
+            id: id.clone(),
+            versions: match range {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Array(items) => items
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" || "),
+                _ => "*".to_string(),
+            },
+            optional: false,
+        })
+        .collect()
+}
 
-        let clean = sanitize_json(&txt);
+/// Fabric people: plain names or `{"name": ..., "contact": {...}}` objects.
+fn fabric_people(val: Option<&serde_json::Value>) -> Vec<String> {
+    let Some(serde_json::Value::Array(items)) = val else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| match item {
+            serde_json::Value::String(name) => Some(name.clone()),
+            serde_json::Value::Object(_) => {
+                item.get("name").and_then(|v| v.as_str()).map(String::from)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// A license field: one id, an array of ids, or (Quilt) objects with
+/// `id`/`name`/`url`.
+fn licenses(val: Option<&serde_json::Value>) -> Vec<String> {
+    let one = |v: &serde_json::Value| match v {
+        serde_json::Value::String(id) => Some(id.clone()),
+        serde_json::Value::Object(_) => v
+            .get("id")
+            .or_else(|| v.get("name"))
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        _ => None,
+    };
+    match val {
+        Some(serde_json::Value::Array(items)) => items.iter().filter_map(one).collect(),
+        Some(v) => one(v).into_iter().collect(),
+        None => Vec::new(),
+    }
+}
+
+/// An icon field: a path, or an object of pixel size -> path, in which case
+/// the largest icon is taken.
+fn icon_path(val: Option<&serde_json::Value>) -> String {
+    match val {
+        Some(serde_json::Value::String(path)) => path.clone(),
+        Some(serde_json::Value::Object(sizes)) => sizes
+            .iter()
+            .filter_map(|(size, path)| Some((size.parse::<u32>().unwrap_or(0), path.as_str()?)))
+            .max_by_key(|(size, _)| *size)
+            .map(|(_, path)| path.to_string())
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Copy description, license, contact links and icon from a Fabric root
+/// object or a Quilt `metadata` block, which share these keys.
+fn read_credits(jar_mod: &mut JarMod, val: &serde_json::Value) {
+    jar_mod.description = str_field(val, "description");
+    jar_mod.license = licenses(val.get("license"));
+    jar_mod.icon = icon_path(val.get("icon"));
+    if let Some(contact) = val.get("contact") {
+        jar_mod.homepage = str_field(contact, "homepage");
+        jar_mod.sources = str_field(contact, "sources");
+        jar_mod.issues = str_field(contact, "issues");
+    }
+}
 
-        // Try full JSON parse
-        if let Ok(val) = serde_json::from_str::<serde_json::Value>(&clean) {
-            let id = val
-                .get("id")
+/// Read the Quilt schema: everything lives under the `quilt_loader` block,
+/// with the display name in `quilt_loader.metadata.name`.
+fn parse_quilt_schema(ql: &serde_json::Value) -> JarMod {
+    let name = ql
+        .get("metadata")
+        .map(|m| str_field(m, "name"))
+        .unwrap_or_default();
+
+    let mut jar_mod = JarMod {
+        id: str_field(ql, "id"),
+        name,
+        version: str_field(ql, "version"),
+        loader: "quilt".to_string(),
+        provides: provided_ids(ql.get("provides")),
+        depends: quilt_dependencies(ql.get("depends")),
+        breaks: quilt_dependencies(ql.get("breaks")),
+        jars: ql
+            .get("jars")
+            .and_then(|v| v.as_array())
+            .map(|items| items.iter().filter_map(|v| v.as_str()).map(String::from).collect())
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+
+    if let Some(metadata) = ql.get("metadata") {
+        read_credits(&mut jar_mod, metadata);
+        // Contributors map a name to a role.
+        if let Some(serde_json::Value::Object(people)) = metadata.get("contributors") {
+            for (person, role) in people {
+                let role = role.as_str().unwrap_or("").to_lowercase();
+                if role == "owner" || role == "author" {
+                    jar_mod.authors.push(person.clone());
+                } else {
+                    jar_mod.contributors.push(person.clone());
+                }
+            }
+        }
+    }
+    jar_mod
+}
+
+fn parsed_provenance(repairs: &[Repair]) -> Provenance {
+    if repairs.is_empty() {
+        Provenance::Parsed
+    } else {
+        Provenance::Repaired
+    }
+}
+
+/// Parse fabric.mod.json / quilt.mod.json. The error is the JSON parser's
+/// message when even the regex fallback finds nothing.
+fn parse_mod_json(txt: &str, quilt_file: bool) -> Result<JarMod, String> {
+    let clean = lenient_json::repair(txt);
+
+    // Try full JSON parse
+    let json_error = match serde_json::from_str::<serde_json::Value>(&clean.text) {
+        Ok(val) => {
+            let mut jar_mod = parse_fabric_json(&val, quilt_file);
+            jar_mod.provenance = parsed_provenance(&clean.repairs);
+            jar_mod.repairs = clean.repairs;
+            return Ok(jar_mod);
+        }
+        Err(e) => e.to_string(),
+    };
+
+    // Regex fallback
+    let loader = if quilt_file { "quilt" } else { "fabric" };
+    regex_fallback(&clean.text, "id")
+        .map(|(id, name, version)| JarMod {
+            id,
+            name,
+            version,
+            loader: loader.to_string(),
+            repairs: clean.repairs,
+            provenance: Provenance::Regex,
+            ..Default::default()
+        })
+        .ok_or(json_error)
+}
+
+/// Build a mod from a parsed fabric.mod.json / quilt.mod.json document.
+fn parse_fabric_json(val: &serde_json::Value, quilt_file: bool) -> JarMod {
+    if let Some(ql) = val.get("quilt_loader") {
+        let mut jar_mod = parse_quilt_schema(ql);
+        jar_mod.environment =
+            Environment::from_json(val.get("minecraft").and_then(|mc| mc.get("environment")));
+        return jar_mod;
+    }
+
+    // Left empty when missing, so the manifest/filename fallback applies.
+    let version = str_field(val, "version");
+
+    // A fabric.mod.json that requires quilt_loader only runs on Quilt.
+    let loader = if quilt_file
+        || val
+            .get("depends")
+            .and_then(|d| d.get("quilt_loader"))
+            .is_some()
+    {
+        "quilt"
+    } else {
+        "fabric"
+    };
+
+    let mut jar_mod = JarMod {
+        id: str_field(val, "id"),
+        name: str_field(val, "name"),
+        version,
+        loader: loader.to_string(),
+        provides: provided_ids(val.get("provides")),
+        depends: fabric_dependencies(val.get("depends")),
+        recommends: fabric_dependencies(val.get("recommends")),
+        suggests: fabric_dependencies(val.get("suggests")),
+        breaks: fabric_dependencies(val.get("breaks")),
+        conflicts: fabric_dependencies(val.get("conflicts")),
+        jars: val
+            .get("jars")
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|j| j.get("file").and_then(|f| f.as_str()))
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        authors: fabric_people(val.get("authors")),
+        contributors: fabric_people(val.get("contributors")),
+        environment: Environment::from_json(val.get("environment")),
+        ..Default::default()
+    };
+    read_credits(&mut jar_mod, val);
+    jar_mod
+}
+
+/// Parse a Forge `mods.toml` / NeoForge `neoforge.mods.toml`. Every `[[mods]]`
+/// entry becomes its own mod, so multi-mod jars are tracked per mod id.
+/// `${file.jarVersion}` is left for [`resolve_versions`] to fill in.
+fn parse_mods_toml(txt: &str, neoforge_file: bool) -> Result<Vec<JarMod>, String> {
+    let txt = txt.trim_start_matches('\u{feff}');
+    let val = txt
+        .parse::<toml::Table>()
+        .map_err(|e| e.message().to_string())?;
+
+    // Older NeoForge releases (1.20.1 - 1.20.4) still ship `mods.toml`; they
+    // are recognized by declaring a dependency on `neoforge`.
+    let depends_on_neoforge = val
+        .get("dependencies")
+        .and_then(|d| d.as_table())
+        .map(|deps| {
+            deps.values()
+                .filter_map(|list| list.as_array())
+                .flatten()
+                .any(|dep| dep.get("modId").and_then(|v| v.as_str()) == Some("neoforge"))
+        })
+        .unwrap_or(false);
+    let loader = if neoforge_file || depends_on_neoforge {
+        "neoforge"
+    } else {
+        "forge"
+    };
+    // Forge's optional top-level `clientSideOnly`; without it the side is unknown.
+    let environment = if val.get("clientSideOnly").and_then(|v| v.as_bool()) == Some(true) {
+        Environment::Client
+    } else {
+        Environment::Unknown
+    };
+
+    let Some(mods) = val.get("mods").and_then(|m| m.as_array()) else {
+        return Err("no [[mods]] entries".to_string());
+    };
+
+    let mods: Vec<JarMod> = mods
+        .iter()
+        .filter_map(|m| {
+            let id = m.get("modId").and_then(|v| v.as_str())?.to_string();
+            // `displayName` is optional; the mod id reads better than the jar stem.
+            let name = m
+                .get("displayName")
+                .and_then(|v| v.as_str())
+                .unwrap_or(&id)
+                .to_string();
+            let version = m
+                .get("version")
                 .and_then(|v| v.as_str())
                 .unwrap_or("")
                 .to_string();
-            let name = val
+
+            let mut jar_mod = JarMod {
+                id,
+                name,
+                version,
+                loader: loader.to_string(),
+                environment,
+                ..Default::default()
+            };
+            add_toml_dependencies(&mut jar_mod, &val);
+            Some(jar_mod)
+        })
+        .collect();
+    if mods.is_empty() {
+        return Err("no [[mods]] entry has a modId".to_string());
+    }
+    Ok(mods)
+}
+
+/// Sort the `[[dependencies.<modId>]]` entries of a mods.toml into the Fabric
+/// relation kinds. Old Forge uses `mandatory`, NeoForge uses `type`.
+fn add_toml_dependencies(jar_mod: &mut JarMod, val: &toml::Table) {
+    let Some(entries) = val
+        .get("dependencies")
+        .and_then(|d| d.get(jar_mod.id.as_str()))
+        .and_then(|d| d.as_array())
+    else {
+        return;
+    };
+
+    for entry in entries {
+        let Some(id) = entry.get("modId").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let dep = Dependency {
+            id: id.to_string(),
+            versions: entry
+                .get("versionRange")
+                .and_then(|v| v.as_str())
+                .unwrap_or("*")
+                .to_string(),
+            optional: false,
+        };
+        let kind = entry
+            .get("type")
+            .and_then(|v| v.as_str())
+            .map(|t| t.to_lowercase())
+            .unwrap_or_else(|| {
+                match entry.get("mandatory").and_then(|v| v.as_bool()) {
+                    Some(false) => "optional".to_string(),
+                    _ => "required".to_string(),
+                }
+            });
+        match kind.as_str() {
+            "optional" => jar_mod.suggests.push(dep),
+            "incompatible" => jar_mod.breaks.push(dep),
+            "discouraged" => jar_mod.conflicts.push(dep),
+            _ => jar_mod.depends.push(dep),
+        }
+    }
+}
+
+/// Parse a legacy Forge `mcmod.info` (1.7.10 - 1.12.2). Both the bare JSON
+/// array and the `{"modListVersion": 2, "modList": [...]}` form are accepted.
+/// These files are frequently malformed, so they go through the same
+/// lenient reader and regex fallback as the Fabric metadata.
+fn parse_mcmod_info(txt: &str) -> Result<Vec<JarMod>, String> {
+    let clean = lenient_json::repair(txt);
+    let loader = "forge-legacy";
+
+    let val = match serde_json::from_str::<serde_json::Value>(&clean.text) {
+        Ok(val) => val,
+        Err(e) => {
+            // Regex fallback
+            return regex_fallback(&clean.text, "modid")
+                .map(|(id, name, version)| {
+                    vec![JarMod {
+                        id,
+                        name,
+                        version,
+                        loader: loader.to_string(),
+                        repairs: clean.repairs,
+                        provenance: Provenance::Regex,
+                        ..Default::default()
+                    }]
+                })
+                .ok_or_else(|| e.to_string());
+        }
+    };
+
+    let list = match &val {
+        serde_json::Value::Array(items) => items.as_slice(),
+        serde_json::Value::Object(_) => val
+            .get("modList")
+            .and_then(|v| v.as_array())
+            .map(|v| v.as_slice())
+            .unwrap_or_default(),
+        _ => &[],
+    };
+
+    let mods: Vec<JarMod> = list
+        .iter()
+        .filter_map(|m| {
+            let id = m.get("modid").and_then(|v| v.as_str())?.to_string();
+            let name = m
                 .get("name")
                 .and_then(|v| v.as_str())
-                .unwrap_or("")
+                .unwrap_or(&id)
                 .to_string();
-            let version = val
+            let version = m
                 .get("version")
                 .and_then(|v| v.as_str())
-                .unwrap_or("unknown")
+                .unwrap_or("")
                 .to_string();
 
-            let loader = if val.get("depends").is_some()
-                && val["depends"].get("quilt_loader").is_some()
-            {
-                "quilt"
-            } else {
-                "fabric"
-            };
+            Some(JarMod {
+                id,
+                name,
+                version,
+                loader: loader.to_string(),
+                repairs: clean.repairs.clone(),
+                provenance: parsed_provenance(&clean.repairs),
+                ..Default::default()
+            })
+        })
+        .collect();
+    if mods.is_empty() {
+        return Err("no mod entry has a modid".to_string());
+    }
+    Ok(mods)
+}
+
+/// A Forge Jar-in-Jar entry from `META-INF/jarjar/metadata.json`. The
+/// artifact coordinates stand in for the id/version of plain libraries.
+struct JarJarEntry {
+    path: String,
+    artifact: String,
+    version: String,
+}
 
-            return Some((id, name, version, loader.to_string()));
+fn parse_jarjar_metadata(txt: &str) -> Vec<JarJarEntry> {
+    let Ok(val) = serde_json::from_str::<serde_json::Value>(&lenient_json::repair(txt).text) else {
+        return Vec::new();
+    };
+    let Some(jars) = val.get("jars").and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+
+    jars.iter()
+        .filter_map(|j| {
+            Some(JarJarEntry {
+                path: j.get("path").and_then(|v| v.as_str())?.to_string(),
+                artifact: j
+                    .get("identifier")
+                    .map(|i| str_field(i, "artifact"))
+                    .unwrap_or_default(),
+                version: j
+                    .get("version")
+                    .map(|v| str_field(v, "artifactVersion"))
+                    .unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Read mods from the metadata files at the root of an archive. Understands
+/// fabric.mod.json, quilt.mod.json, META-INF/neoforge.mods.toml,
+/// META-INF/mods.toml and the legacy mcmod.info; the last three may yield
+/// several mods from a single jar. When no file yields a mod, the first
+/// parse error wins over "no metadata".
+fn read_root_meta<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+) -> Result<Vec<JarMod>, MetaError> {
+    let mut parse_error: Option<String> = None;
+    let read = |archive: &mut zip::ZipArchive<R>, candidate: &str| {
+        read_entry_text(archive, candidate)
+            .map_err(|e| (FailureReason::Corrupt, Some(format!("{candidate}: {e}"))))
+    };
+
+    for (candidate, quilt_file) in [("fabric.mod.json", false), ("quilt.mod.json", true)] {
+        let Some(txt) = read(archive, candidate)? else {
+            continue;
+        };
+        match parse_mod_json(&txt, quilt_file) {
+            Ok(m) => return Ok(vec![m]),
+            Err(e) => {
+                parse_error.get_or_insert(format!("{candidate}: {e}"));
+            }
         }
+    }
+
+    for (candidate, neoforge_file) in [
+        ("META-INF/neoforge.mods.toml", true),
+        ("META-INF/mods.toml", false),
+    ] {
+        let Some(txt) = read(archive, candidate)? else {
+            continue;
+        };
+        match parse_mods_toml(&txt, neoforge_file) {
+            Ok(mods) => return Ok(mods),
+            Err(e) => {
+                parse_error.get_or_insert(format!("{candidate}: {e}"));
+            }
+        }
+    }
+
+    if let Some(txt) = read(archive, "mcmod.info")? {
+        match parse_mcmod_info(&txt) {
+            Ok(mods) => return Ok(mods),
+            Err(e) => {
+                parse_error.get_or_insert(format!("mcmod.info: {e}"));
+            }
+        }
+    }
+
+    Err(match parse_error {
+        Some(detail) => (FailureReason::Unparseable, Some(detail)),
+        None => (FailureReason::NoMetadata, None),
+    })
+}
 
-        // Regex fallback
-        if let Some((id, name, version)) = regex_fallback(&clean) {
-            return Some((id, name, version, "fabric".to_string()));
+/// Build-tool placeholders that were never expanded: Gradle `${version}`,
+/// `$version`, Ant-style `@VERSION@`.
+static PLACEHOLDER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\$\{[^}]*\}|\$[A-Za-z_][A-Za-z0-9_.]*|@[A-Za-z_][A-Za-z0-9_.]*@").unwrap()
+});
+
+fn is_unresolved_version(version: &str) -> bool {
+    version.is_empty() || PLACEHOLDER_RE.is_match(version)
+}
+
+fn is_single_placeholder(version: &str) -> bool {
+    let version = version.trim();
+    PLACEHOLDER_RE
+        .find(version)
+        .is_some_and(|m| m.start() == 0 && m.end() == version.len())
+}
+
+/// Fill placeholder versions from the manifest's `Implementation-Version`.
+/// Forge's `${file.jarVersion}` is the common case. Only a version that is a
+/// single placeholder is replaced; in `${mc_version}-${version}` the
+/// manifest can't tell which part is which, so it stays unresolved. Whatever
+/// stays unresolved falls through to the filename in [`to_mod_data`].
+fn resolve_versions(mods: &mut [JarMod], manifest: Option<&str>) {
+    let jar_version = manifest
+        .and_then(|m| manifest_attr(m, "Implementation-Version"))
+        .filter(|v| !is_unresolved_version(v));
+    let Some(jar_version) = jar_version else {
+        return;
+    };
+
+    for m in mods.iter_mut() {
+        if m.version.is_empty() || is_single_placeholder(&m.version) {
+            m.version = jar_version.clone();
+            m.version_source = VersionSource::Manifest;
+        }
+    }
+}
+
+/// Minecraft versions (`1.20.1`, `1.12`) that filenames carry alongside the
+/// mod version.
+static MC_VERSION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^1\.([7-9]|[1-9][0-9])(\.[0-9]+)?$").unwrap());
+
+/// Guess a mod version from a jar file stem such as `sodium-fabric-0.5.8+mc1.20.4`
+/// or `jei-1.20.1-forge-15.2.0.27`: the last `-`/`_` separated segment that
+/// starts with a digit and isn't a Minecraft version. A Minecraft version is
+/// only taken when nothing else looks like a version.
+fn version_from_filename(stem: &str) -> Option<String> {
+    let stem = stem.trim_end_matches(".jar");
+    let candidates: Vec<&str> = stem
+        .split(['-', '_'])
+        .map(|part| part.strip_prefix(['v', 'V']).unwrap_or(part))
+        .filter(|part| part.starts_with(|c: char| c.is_ascii_digit()) && part.contains('.'))
+        .collect();
+    candidates
+        .iter()
+        .rev()
+        .find(|part| !MC_VERSION_RE.is_match(part))
+        .or(candidates.last())
+        .map(|part| part.to_string())
+}
+
+/// Read mods from an archive, then recurse into the jars it bundles. Nested
+/// jars are attached to the first mod of the host archive.
+fn read_meta_from_archive<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    depth: usize,
+) -> Result<Vec<JarMod>, MetaError> {
+    let mut mods = read_root_meta(archive)?;
+    let manifest = read_entry_text(archive, "META-INF/MANIFEST.MF").ok().flatten();
+    resolve_versions(&mut mods, manifest.as_deref());
+    if depth >= MAX_NESTING_DEPTH {
+        return Ok(mods);
+    }
+
+    let mut jarjar: Vec<JarJarEntry> = read_entry_text(archive, "META-INF/jarjar/metadata.json")
+        .ok()
+        .flatten()
+        .map(|txt| parse_jarjar_metadata(&txt))
+        .unwrap_or_default();
+    for path in &mods[0].jars {
+        if !jarjar.iter().any(|j| &j.path == path) {
+            jarjar.push(JarJarEntry {
+                path: path.clone(),
+                artifact: String::new(),
+                version: String::new(),
+            });
+        }
+    }
+
+    let host_loader = mods[0].loader.clone();
+    for entry in jarjar {
+        let mut raw = Vec::new();
+        let Ok(mut file) = archive.by_name(&entry.path) else {
+            continue;
+        };
+        if file.read_to_end(&mut raw).is_err() {
+            continue;
         }
+        drop(file);
+
+        let inner = zip::ZipArchive::new(Cursor::new(raw))
+            .ok()
+            .and_then(|mut inner| read_meta_from_archive(&mut inner, depth + 1).ok());
+
+        // Plain libraries carry no mod metadata; describe them by their
+        // Jar-in-Jar coordinates (or just the file name) instead.
+        let inner_mods = inner.unwrap_or_else(|| {
+            vec![JarMod {
+                id: entry.artifact.clone(),
+                name: entry.artifact.clone(),
+                version: entry.version.clone(),
+                loader: host_loader.clone(),
+                ..Default::default()
+            }]
+        });
+
+        mods[0].nested.push(NestedJar {
+            path: entry.path,
+            mods: inner_mods,
+        });
     }
 
-    None
+    Ok(mods)
+}
+
+/// Read mod metadata from the bytes of a JAR file, including the jars nested
+/// inside it.
+fn read_meta_from_jar(bytes: &[u8]) -> Result<Vec<JarMod>, MetaError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(|e| {
+        // Every ZIP starts with a "PK" header; anything else isn't an archive.
+        let reason = if bytes.starts_with(b"PK") {
+            FailureReason::Corrupt
+        } else {
+            FailureReason::NotZip
+        };
+        (reason, Some(e.to_string()))
+    })?;
+    read_meta_from_archive(&mut archive, 0)
+}
+
+/// Hex SHA-1 and SHA-512 of a file's contents.
+fn jar_hashes(bytes: &[u8]) -> (String, String) {
+    use sha1::Digest;
+    (
+        format!("{:x}", sha1::Sha1::digest(bytes)),
+        format!("{:x}", sha2::Sha512::digest(bytes)),
+    )
+}
+
+/// Turn extracted metadata into a snapshot entry, filling in the file stem
+/// for a missing id/name.
+fn to_mod_data(m: JarMod, filename: &str, stem: &str, disabled: bool) -> ModData {
+    let nested = m
+        .nested
+        .into_iter()
+        .flat_map(|jar| {
+            let inner_stem = Path::new(&jar.path)
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            jar.mods
+                .into_iter()
+                .map(|inner| to_mod_data(inner, &jar.path, &inner_stem, disabled))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let (version, version_source) = if !is_unresolved_version(&m.version) {
+        (m.version, m.version_source)
+    } else if let Some(v) = version_from_filename(stem) {
+        (v, VersionSource::Filename)
+    } else {
+        ("unknown".to_string(), VersionSource::Unknown)
+    };
+
+    let provenance = if m.id.is_empty() {
+        Provenance::FileStem
+    } else {
+        m.provenance
+    };
+
+    ModData {
+        filename: filename.to_string(),
+        id: if m.id.is_empty() { stem.to_string() } else { m.id },
+        name: if m.name.is_empty() { stem.to_string() } else { m.name },
+        version,
+        loader: m.loader,
+        disabled,
+        provides: m.provides,
+        depends: m.depends,
+        recommends: m.recommends,
+        suggests: m.suggests,
+        breaks: m.breaks,
+        conflicts: m.conflicts,
+        nested,
+        size: 0,
+        sha1: String::new(),
+        sha512: String::new(),
+        authors: m.authors,
+        contributors: m.contributors,
+        description: m.description,
+        license: m.license,
+        homepage: m.homepage,
+        sources: m.sources,
+        issues: m.issues,
+        icon: m.icon,
+        repairs: m.repairs,
+        version_source,
+        provenance,
+        environment: m.environment,
+    }
+}
+
+/// Result of reading one file from the mods folder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum JarScan {
+    Mods(Vec<ModData>),
+    Failed(ScanFailure),
+}
+
+/// Bump whenever extraction changes what ends up in a [`ModData`], so scan
+/// caches written by older builds are thrown away instead of reused.
+pub const EXTRACTOR_VERSION: u32 = 11;
+
+/// File name of the per-profile scan cache, stored next to the snapshots.
+pub const SCAN_CACHE_FILE: &str = "porovnavac.scan_cache.json";
+
+/// Per-profile cache of extracted metadata, keyed by jar file name. An entry
+/// is reused while the jar's size and modification time are unchanged, so
+/// unchanged jars are neither read nor parsed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanCache {
+    extractor_version: u32,
+    entries: HashMap<String, CacheEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    size: u64,
+    mtime_ns: u64,
+    /// SHA-1 of the jar. Lets a jar whose mtime changed but whose content
+    /// didn't (copied, touched, re-downloaded) skip parsing after hashing.
+    sha1: String,
+    result: JarScan,
+}
+
+impl ScanCache {
+    /// Loads the cache, starting empty when it is missing, unreadable or was
+    /// written by a different extractor version.
+    pub fn load(path: &Path) -> ScanCache {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|txt| serde_json::from_str::<ScanCache>(&txt).ok())
+            .filter(|c| c.extractor_version == EXTRACTOR_VERSION)
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) {
+        if let Ok(json) = serde_json::to_string(self) {
+            let _ = std::fs::write(path, json);
+        }
+    }
+
+    fn entry(&self, filename: &str) -> Option<&CacheEntry> {
+        if self.extractor_version != EXTRACTOR_VERSION {
+            return None;
+        }
+        self.entries.get(filename)
+    }
+}
+
+/// Size and modification time of a file, or `None` when the platform
+/// doesn't report an mtime (such files are never cached).
+fn file_stamp(path: &Path) -> Option<(u64, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime = meta.modified().ok()?.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some((meta.len(), mtime.as_nanos() as u64))
+}
+
+/// Scan one jar, reusing the cached result when its size and mtime match, or
+/// when its content hash matches. Returns the scan, the cache entry to keep
+/// for next time, and whether the result came from the cache.
+fn scan_jar_cached(jar: &Path, cache: &ScanCache) -> (JarScan, Option<CacheEntry>, bool) {
+    let filename = jar.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let stamp = file_stamp(jar);
+    let previous = cache.entry(&filename);
+
+    if let (Some(prev), Some((size, mtime_ns))) = (previous, stamp) {
+        if prev.size == size && prev.mtime_ns == mtime_ns {
+            return (prev.result.clone(), Some(prev.clone()), true);
+        }
+    }
+
+    // Read the whole jar once: it is both hashed and parsed from memory.
+    let bytes = match std::fs::read(jar) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            let failure = ScanFailure {
+                filename,
+                reason: FailureReason::Io,
+                detail: Some(e.to_string()),
+                size: stamp.map_or(0, |(size, _)| size),
+                sha1: String::new(),
+                sha512: String::new(),
+            };
+            return (JarScan::Failed(failure), None, false);
+        }
+    };
+    let (sha1, sha512) = jar_hashes(&bytes);
+
+    let reused = previous.filter(|prev| !prev.sha1.is_empty() && prev.sha1 == sha1);
+    let (result, hit) = match reused {
+        Some(prev) => (prev.result.clone(), true),
+        None => (scan_jar(jar, &bytes, &sha1, &sha512), false),
+    };
+
+    let entry = stamp.map(|(size, mtime_ns)| CacheEntry {
+        size,
+        mtime_ns,
+        sha1,
+        result: result.clone(),
+    });
+    (result, entry, hit)
+}
+
+/// Parse one jar that has already been read into memory and hashed.
+fn scan_jar(jar: &Path, bytes: &[u8], sha1: &str, sha512: &str) -> JarScan {
+    let filename = jar.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let is_disabled = filename.ends_with(".jar.disabled");
+
+    let mods = match read_meta_from_jar(bytes) {
+        Ok(mods) => mods,
+        Err((reason, detail)) => {
+            // Parser messages can span lines; keep each failure on one.
+            let detail = detail.map(|d| d.split_whitespace().collect::<Vec<_>>().join(" "));
+            return JarScan::Failed(ScanFailure {
+                filename,
+                reason,
+                detail,
+                size: bytes.len() as u64,
+                sha1: sha1.to_string(),
+                sha512: sha512.to_string(),
+            });
+        }
+    };
+
+    let stem = jar.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let mods = mods
+        .into_iter()
+        .map(|m| {
+            let mut mod_data = to_mod_data(m, &filename, &stem, is_disabled);
+            mod_data.size = bytes.len() as u64;
+            mod_data.sha1 = sha1.to_string();
+            mod_data.sha512 = sha512.to_string();
+            mod_data
+        })
+        .collect();
+    JarScan::Mods(mods)
 }
 
-/// Scan all .jar and .jar.disabled files in a directory.
-pub fn scan_mods_directory(mods_dir: &Path) -> Snapshot {
+/// The failure recorded for a jar whose scan panicked, with the panic message
+/// and, if the file can be read, its size and hashes.
+fn crashed_scan(
+    jar: &Path,
+    payload: Box<dyn std::any::Any + Send>,
+) -> (JarScan, Option<CacheEntry>, bool) {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned());
+    let (size, sha1, sha512) = match std::fs::read(jar) {
+        Ok(bytes) => {
+            let (sha1, sha512) = jar_hashes(&bytes);
+            (bytes.len() as u64, sha1, sha512)
+        }
+        Err(_) => (0, String::new(), String::new()),
+    };
+    let failure = ScanFailure {
+        filename: jar.file_name().unwrap_or_default().to_string_lossy().to_string(),
+        reason: FailureReason::Crashed,
+        detail: message,
+        size,
+        sha1,
+        sha512,
+    };
+    (JarScan::Failed(failure), None, false)
+}
+
+/// Number of scan threads used by [`scan_mods_directory`].
+pub fn default_scan_threads() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(8)
+}
+
+/// Scan all .jar and .jar.disabled files in a directory. Unchanged jars are
+/// taken from `cache`, which is then replaced with entries for exactly the
+/// jars seen in this scan.
+pub fn scan_mods_directory(mods_dir: &Path, cache: &mut ScanCache) -> Snapshot {
+    scan_mods_directory_with_threads(mods_dir, cache, default_scan_threads())
+}
+
+/// [`scan_mods_directory`] with an explicit thread count. Jars are handed out
+/// to the workers one at a time, and results are stored by file index, so
+/// the snapshot order is the same (sorted by filename) for any thread count.
+pub fn scan_mods_directory_with_threads(
+    mods_dir: &Path,
+    cache: &mut ScanCache,
+    threads: usize,
+) -> Snapshot {
     let mut all_files: Vec<PathBuf> = Vec::new();
 
     if let Ok(entries) = std::fs::read_dir(mods_dir) {
@@ -190,39 +1512,80 @@ pub fn scan_mods_directory(mods_dir: &Path) -> Snapshot {
 
     all_files.sort();
 
+    let next = AtomicUsize::new(0);
+    let threads = threads.clamp(1, all_files.len().max(1));
+    let shared_cache: &ScanCache = cache;
+    let results: Vec<Option<(JarScan, Option<CacheEntry>, bool)>> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(jar) = all_files.get(i) else {
+                            break;
+                        };
+                        // A panic costs only this jar, not the worker's other results.
+                        let scan = std::panic::catch_unwind(|| scan_jar_cached(jar, shared_cache))
+                            .unwrap_or_else(|payload| crashed_scan(jar, payload));
+                        done.push((i, scan));
+                    }
+                    done
+                })
+            })
+            .collect();
+
+        let mut results: Vec<Option<_>> = all_files.iter().map(|_| None).collect();
+        for worker in workers {
+            for (i, scan) in worker.join().unwrap_or_default() {
+                results[i] = Some(scan);
+            }
+        }
+        results
+    });
+
+    let mut new_cache = ScanCache {
+        extractor_version: EXTRACTOR_VERSION,
+        entries: HashMap::new(),
+    };
+    let mut cached = 0;
+
     let mut active = Vec::new();
     let mut disabled = Vec::new();
     let mut failed = Vec::new();
 
-    for jar in &all_files {
+    for (scan, jar) in results.into_iter().zip(&all_files) {
         let filename = jar.file_name().unwrap_or_default().to_string_lossy().to_string();
-        let is_disabled = filename.ends_with(".jar.disabled");
-
-        match read_meta_from_jar(jar) {
-            Some((id, name, version, loader)) => {
-                let stem = jar.file_stem().unwrap_or_default().to_string_lossy().to_string();
-                let mod_data = ModData {
-                    filename: filename.clone(),
-                    id: if id.is_empty() { stem.clone() } else { id },
-                    name: if name.is_empty() { stem } else { name },
-                    version: if version.is_empty() {
-                        "unknown".to_string()
+        // Panics are caught per jar, so this only happens if a worker died
+        // outside of scanning; report the jar as unreadable.
+        let (scan, entry, hit) = scan.unwrap_or_else(|| {
+            let failure = ScanFailure {
+                filename: filename.clone(),
+                reason: FailureReason::Unknown,
+                detail: None,
+                size: 0,
+                sha1: String::new(),
+                sha512: String::new(),
+            };
+            (JarScan::Failed(failure), None, false)
+        });
+        if hit {
+            cached += 1;
+        }
+        if let Some(entry) = entry {
+            new_cache.entries.insert(filename, entry);
+        }
+        match scan {
+            JarScan::Mods(mods) => {
+                for mod_data in mods {
+                    if mod_data.disabled {
+                        disabled.push(mod_data);
                     } else {
-                        version
-                    },
-                    loader,
-                    disabled: is_disabled,
-                };
-
-                if is_disabled {
-                    disabled.push(mod_data);
-                } else {
-                    active.push(mod_data);
+                        active.push(mod_data);
+                    }
                 }
             }
-            None => {
-                failed.push(filename);
-            }
+            JarScan::Failed(failure) => failed.push(failure),
         }
     }
 
@@ -231,7 +1594,10 @@ pub fn scan_mods_directory(mods_dir: &Path) -> Snapshot {
         active: active.len(),
         disabled: disabled.len(),
         failed: failed.len(),
+        cached,
     };
+    *cache = new_cache;
+    let duplicates = find_duplicate_ids(&active);
 
     Snapshot {
         timestamp: chrono::Local::now().to_rfc3339(),
@@ -239,31 +1605,238 @@ pub fn scan_mods_directory(mods_dir: &Path) -> Snapshot {
         active,
         disabled,
         failed,
+        duplicates,
         stats,
     }
 }
 
+// ──────────────────────────────────────────────────────────────────────
+// Dependency check
+// ──────────────────────────────────────────────────────────────────────
+
+/// Ids supplied by the game or the loader itself rather than by a jar.
+const PLATFORM_IDS: [&str; 10] = [
+    "minecraft",
+    "java",
+    "fabricloader",
+    "fabric-loader",
+    "quilt_loader",
+    "forge",
+    "neoforge",
+    "javafml",
+    "lowcodefml",
+    "mcp",
+];
+
+/// An active mod whose required dependency is not active.
+#[derive(Debug, Clone)]
+pub struct MissingDependency {
+    pub mod_name: String,
+    pub dep_id: String,
+    pub versions: String,
+    /// The dependency is in the folder, but disabled.
+    pub disabled: bool,
+}
+
+/// Two active mods where the first declares `breaks` against the second.
+#[derive(Debug, Clone)]
+pub struct BreakingPair {
+    pub mod_name: String,
+    pub other_name: String,
+    pub other_version: String,
+    pub versions: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DependencyReport {
+    pub missing: Vec<MissingDependency>,
+    pub breaking: Vec<BreakingPair>,
+}
+
+impl DependencyReport {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.breaking.is_empty()
+    }
+}
+
+/// Every id a set of mods makes available: their own ids, their `provides`
+/// and everything bundled inside them, mapped to the providing mod.
+fn provided_index(mods: &[ModData]) -> HashMap<&str, &ModData> {
+    fn walk<'a>(m: &'a ModData, out: &mut HashMap<&'a str, &'a ModData>) {
+        out.insert(m.id.as_str(), m);
+        for alias in &m.provides {
+            out.entry(alias.as_str()).or_insert(m);
+        }
+        for inner in &m.nested {
+            walk(inner, out);
+        }
+    }
+    let mut out = HashMap::new();
+    for m in mods {
+        walk(m, &mut out);
+    }
+    out
+}
+
+/// Checks whether the active mods of a snapshot can load together: lists
+/// required dependencies that are missing or only present as disabled jars,
+/// and active mods that declare `breaks` against another active mod whose
+/// version falls in the broken range.
+pub fn check_dependencies(snapshot: &Snapshot) -> DependencyReport {
+    let active = provided_index(&snapshot.active);
+    let disabled = provided_index(&snapshot.disabled);
+    let mut report = DependencyReport::default();
+
+    for m in &snapshot.active {
+        for dep in m.depends.iter().filter(|d| !d.optional) {
+            if PLATFORM_IDS.contains(&dep.id.as_str()) || active.contains_key(dep.id.as_str()) {
+                continue;
+            }
+            report.missing.push(MissingDependency {
+                mod_name: m.name.clone(),
+                dep_id: dep.id.clone(),
+                versions: dep.versions.clone(),
+                disabled: disabled.contains_key(dep.id.as_str()),
+            });
+        }
+
+        for b in &m.breaks {
+            let Some(other) = active.get(b.id.as_str()) else {
+                continue;
+            };
+            // Only flag when the installed version is known to be affected.
+            if matches_range(&other.version, &b.versions) == Some(true) {
+                report.breaking.push(BreakingPair {
+                    mod_name: m.name.clone(),
+                    other_name: other.name.clone(),
+                    other_version: other.version.clone(),
+                    versions: b.versions.clone(),
+                });
+            }
+        }
+    }
+
+    report.missing.sort_by_key(|d| (d.mod_name.to_lowercase(), d.dep_id.clone()));
+    report.breaking.sort_by_key(|b| (b.mod_name.to_lowercase(), b.other_name.to_lowercase()));
+    report
+}
+
 // ──────────────────────────────────────────────────────────────────────
 // Comparison
 // ──────────────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize)]
-pub struct UpdatedMod {
+pub struct UpdatedMod {
+    pub id: String,
+    pub name: String,
+    pub old_version: String,
+    pub new_version: String,
+    pub filename: String,
+    pub kind: UpdateKind,
+}
+
+/// Version change of a mod bundled inside another (Jar-in-Jar).
+#[derive(Debug, Clone, Serialize)]
+pub struct NestedUpdate {
+    pub host_id: String,
+    pub host_name: String,
+    pub id: String,
+    pub name: String,
+    pub old_version: String,
+    pub new_version: String,
+}
+
+/// How a removed mod was linked to the added mod that took its place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchReason {
+    /// One of them lists the other's id in `provides` (e.g. a fork).
+    Provides,
+    /// Identical jar contents under a different id.
+    Hash,
+    /// Same display name once case and punctuation are ignored.
+    Name,
+    /// Same jar filename once version tokens are stripped.
+    Filename,
+}
+
+impl MatchReason {
+    pub fn label(self, lang: Lang) -> &'static str {
+        match self {
+            MatchReason::Provides => T::match_provides(lang),
+            MatchReason::Hash => T::match_hash(lang),
+            MatchReason::Name => T::match_name(lang),
+            MatchReason::Filename => T::match_filename(lang),
+        }
+    }
+}
+
+/// A removed mod and the added mod that took its place.
+#[derive(Debug, Clone)]
+pub struct ReplacedMod {
+    pub old: ModData,
+    pub new: ModData,
+    pub matched_by: MatchReason,
+}
+
+/// A mod whose loader build was swapped (e.g. its Fabric jar for its Quilt one).
+#[derive(Debug, Clone, Serialize)]
+pub struct LoaderChange {
+    pub id: String,
+    pub name: String,
+    pub old_loader: String,
+    pub new_loader: String,
+}
+
+/// A mod whose declared environment changed (e.g. client-only to both sides).
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentChange {
     pub id: String,
     pub name: String,
-    pub old_version: String,
-    pub new_version: String,
-    pub filename: String,
+    pub old_environment: Environment,
+    pub new_environment: Environment,
+}
+
+/// A mod that was switched on or off, with its entry from both snapshots so
+/// a version change in the same step isn't lost.
+#[derive(Debug, Clone)]
+pub struct ToggledMod {
+    pub old: ModData,
+    pub new: ModData,
+    /// The version differs beyond what [`CompareOptions::normalization`] ignores.
+    pub version_changed: bool,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct Changes {
     pub added: Vec<ModData>,
     pub removed: Vec<ModData>,
+    /// Every version change, downgrades included; see [`UpdatedMod::kind`].
     pub updated: Vec<UpdatedMod>,
-    pub newly_disabled: Vec<ModData>,
-    pub newly_enabled: Vec<ModData>,
+    /// Removed/added pairs that are the same mod under a new id, or a
+    /// replacement for it; see [`MatchReason`].
+    pub replaced: Vec<ReplacedMod>,
+    /// Version changed only in build metadata, MC-version or loader suffix
+    /// (only filled when [`CompareOptions::list_suffix_only`] is set).
+    pub suffix_only: Vec<UpdatedMod>,
+    /// Same version string, different jar contents (a silent re-upload).
+    pub rebuilt: Vec<ModData>,
+    /// Mods that stayed active but switched loader or environment. These
+    /// come on top of any version change of the same mod.
+    pub loader_changed: Vec<LoaderChange>,
+    pub environment_changed: Vec<EnvironmentChange>,
+    pub nested_updated: Vec<NestedUpdate>,
+    pub newly_disabled: Vec<ToggledMod>,
+    pub newly_enabled: Vec<ToggledMod>,
+    /// Version changes of mods that are disabled in both snapshots.
+    pub disabled_updated: Vec<UpdatedMod>,
     pub unchanged: Vec<ModData>,
+    /// Mods disabled in the new snapshot, for the "currently disabled" list.
+    pub currently_disabled: Vec<ModData>,
+    /// Changes of mods the profile's filter files under libraries, shown in
+    /// a collapsed section; see [`ModFilter::apply`].
+    pub libraries: Option<Box<Changes>>,
+    /// Changes of mods the filter keeps out of the public changelog.
+    pub internal: Option<Box<Changes>>,
 }
 
 impl Changes {
@@ -271,35 +1844,264 @@ impl Changes {
         self.added.len()
             + self.removed.len()
             + self.updated.len()
+            + self.replaced.len()
+            + self.suffix_only.len()
+            + self.rebuilt.len()
+            + self.loader_changed.len()
+            + self.environment_changed.len()
+            + self.nested_updated.len()
             + self.newly_disabled.len()
             + self.newly_enabled.len()
+            + self.disabled_updated.len()
+            + self.libraries.as_ref().map_or(0, |c| c.total_changes())
+    }
+
+    /// Append every list of `other` to the matching list of `self`.
+    fn merge(&mut self, other: Changes) {
+        self.added.extend(other.added);
+        self.removed.extend(other.removed);
+        self.updated.extend(other.updated);
+        self.replaced.extend(other.replaced);
+        self.suffix_only.extend(other.suffix_only);
+        self.rebuilt.extend(other.rebuilt);
+        self.loader_changed.extend(other.loader_changed);
+        self.environment_changed.extend(other.environment_changed);
+        self.nested_updated.extend(other.nested_updated);
+        self.newly_disabled.extend(other.newly_disabled);
+        self.newly_enabled.extend(other.newly_enabled);
+        self.disabled_updated.extend(other.disabled_updated);
+        self.unchanged.extend(other.unchanged);
+        self.currently_disabled.extend(other.currently_disabled);
+    }
+
+    /// These changes as the internal changelog sees them: internal-only
+    /// mods listed with the rest.
+    pub fn with_internal(&self) -> Changes {
+        let mut all = self.clone();
+        if let Some(internal) = all.internal.take() {
+            all.merge(*internal);
+        }
+        all
+    }
+}
+
+/// All mods nested inside `m`, at any depth, keyed by id.
+fn nested_by_id(m: &ModData) -> HashMap<&str, &ModData> {
+    fn walk<'a>(m: &'a ModData, out: &mut HashMap<&'a str, &'a ModData>) {
+        for inner in &m.nested {
+            out.insert(inner.id.as_str(), inner);
+            walk(inner, out);
+        }
+    }
+    let mut out = HashMap::new();
+    walk(m, &mut out);
+    out
+}
+
+/// Version changes of bundled mods between two builds of the same host mod.
+fn compare_nested(old_host: &ModData, new_host: &ModData) -> Vec<NestedUpdate> {
+    let old_nested = nested_by_id(old_host);
+    nested_by_id(new_host)
+        .into_iter()
+        .filter_map(|(id, m)| {
+            let old_m = old_nested.get(id)?;
+            (old_m.version != m.version).then(|| NestedUpdate {
+                host_id: new_host.id.clone(),
+                host_name: new_host.name.clone(),
+                id: id.to_string(),
+                name: m.name.clone(),
+                old_version: old_m.version.clone(),
+                new_version: m.version.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Lowercased display name without spaces or punctuation.
+fn normalized_name(m: &ModData) -> String {
+    m.name
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Jar filename without extension and without the segments that carry
+/// versions: `sodium-fabric-0.5.8+mc1.20.1.jar` -> `sodium-fabric`.
+fn filename_key(m: &ModData) -> String {
+    let stem = m
+        .filename
+        .trim_end_matches(".disabled")
+        .trim_end_matches(".jar");
+    stem.split(['-', '_', '+', ' '])
+        .filter(|part| {
+            let rest = part
+                .strip_prefix("mc")
+                .or_else(|| part.strip_prefix(['v', 'V']))
+                .unwrap_or(part);
+            !part.is_empty() && !rest.starts_with(|c: char| c.is_ascii_digit())
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
+fn same_contents(a: &ModData, b: &ModData) -> bool {
+    (!a.sha512.is_empty() && a.sha512 == b.sha512) || (!a.sha1.is_empty() && a.sha1 == b.sha1)
+}
+
+/// Pair up removed and added mods that are the same mod under a new id, or
+/// a replacement for it, and move them into `replaced`. Passes run from the
+/// most to the least reliable evidence; each mod is paired at most once.
+/// Both lists are sorted by id first so the pairing doesn't depend on map order.
+fn match_unpaired(changes: &mut Changes) {
+    changes.removed.sort_by(|a, b| a.id.cmp(&b.id));
+    changes.added.sort_by(|a, b| a.id.cmp(&b.id));
+
+    match_pass(changes, MatchReason::Provides, |old, new| {
+        new.provides.contains(&old.id) || old.provides.contains(&new.id)
+    });
+    match_pass(changes, MatchReason::Hash, same_contents);
+    match_pass(changes, MatchReason::Name, |old, new| {
+        let name = normalized_name(old);
+        !name.is_empty() && name == normalized_name(new)
+    });
+    match_pass(changes, MatchReason::Filename, |old, new| {
+        let key = filename_key(old);
+        !key.is_empty() && key == filename_key(new)
+    });
+}
+
+fn match_pass(
+    changes: &mut Changes,
+    reason: MatchReason,
+    same: impl Fn(&ModData, &ModData) -> bool,
+) {
+    let mut i = 0;
+    while i < changes.removed.len() {
+        let old = &changes.removed[i];
+        match changes.added.iter().position(|new| same(old, new)) {
+            Some(j) => {
+                let new = changes.added.remove(j);
+                let old = changes.removed.remove(i);
+                changes.replaced.push(ReplacedMod {
+                    old,
+                    new,
+                    matched_by: reason,
+                });
+            }
+            None => i += 1,
+        }
+    }
+}
+
+/// Record a loader or environment switch. An unknown environment on either
+/// side (Forge metadata, older snapshots) is not a change.
+fn compare_platform(old: &ModData, new: &ModData, changes: &mut Changes) {
+    if !old.loader.is_empty() && !new.loader.is_empty() && old.loader != new.loader {
+        changes.loader_changed.push(LoaderChange {
+            id: new.id.clone(),
+            name: new.name.clone(),
+            old_loader: old.loader.clone(),
+            new_loader: new.loader.clone(),
+        });
+    }
+    if old.environment != Environment::Unknown
+        && new.environment != Environment::Unknown
+        && old.environment != new.environment
+    {
+        changes.environment_changed.push(EnvironmentChange {
+            id: new.id.clone(),
+            name: new.name.clone(),
+            old_environment: old.environment,
+            new_environment: new.environment,
+        });
+    }
+}
+
+/// Whether the jar contents changed without a version bump. Snapshots taken
+/// before hashing existed have no hashes and never count as rebuilt.
+fn is_rebuilt(old: &ModData, new: &ModData) -> bool {
+    if !old.sha512.is_empty() && !new.sha512.is_empty() {
+        return old.sha512 != new.sha512;
+    }
+    !old.sha1.is_empty() && !new.sha1.is_empty() && old.sha1 != new.sha1
+}
+
+/// How [`compare_snapshots`] decides what counts as an update.
+#[derive(Debug, Clone, Default)]
+pub struct CompareOptions {
+    /// Version decorations to ignore; a change only in those is "suffix-only".
+    pub normalization: Normalization,
+    /// List suffix-only changes in `Changes::suffix_only` instead of
+    /// counting those mods as unchanged.
+    pub list_suffix_only: bool,
+    /// The profile's ignore rules, applied to every comparison.
+    pub filter: ModFilter,
+}
+
+fn updated_mod(old: &ModData, new: &ModData) -> UpdatedMod {
+    UpdatedMod {
+        id: new.id.clone(),
+        name: new.name.clone(),
+        old_version: old.version.clone(),
+        new_version: new.version.clone(),
+        filename: new.filename.clone(),
+        kind: classify_update(&old.version, &new.version),
     }
 }
 
-pub fn compare_snapshots(old: &Snapshot, new: &Snapshot) -> Changes {
-    let old_active: HashMap<&str, &ModData> = old.active.iter().map(|m| (m.id.as_str(), m)).collect();
-    let new_active: HashMap<&str, &ModData> = new.active.iter().map(|m| (m.id.as_str(), m)).collect();
-    let old_disabled: HashMap<&str, &ModData> =
-        old.disabled.iter().map(|m| (m.id.as_str(), m)).collect();
-    let new_disabled: HashMap<&str, &ModData> =
-        new.disabled.iter().map(|m| (m.id.as_str(), m)).collect();
+/// Mods keyed by id, iterated in id order. When an id is duplicated (see
+/// [`Snapshot::duplicates`]) the jar with the first filename wins, so the
+/// comparison doesn't depend on which copy happens to be seen last.
+fn index_by_id(mods: &[ModData]) -> BTreeMap<&str, &ModData> {
+    let mut out: BTreeMap<&str, &ModData> = BTreeMap::new();
+    for m in mods {
+        out.entry(m.id.as_str())
+            .and_modify(|kept| {
+                if m.filename < kept.filename {
+                    *kept = m;
+                }
+            })
+            .or_insert(m);
+    }
+    out
+}
+
+pub fn compare_snapshots(old: &Snapshot, new: &Snapshot, options: &CompareOptions) -> Changes {
+    let old_active = index_by_id(&old.active);
+    let new_active = index_by_id(&new.active);
+    let old_disabled = index_by_id(&old.disabled);
+    let new_disabled = index_by_id(&new.disabled);
 
     let mut changes = Changes::default();
+    let toggled = |old: &ModData, new: &ModData| ToggledMod {
+        old: old.clone(),
+        new: new.clone(),
+        version_changed: normalize_version(&old.version, &options.normalization)
+            != normalize_version(&new.version, &options.normalization),
+    };
 
     for (mod_id, m) in &new_active {
         if !old_active.contains_key(mod_id) && !old_disabled.contains_key(mod_id) {
             changes.added.push((*m).clone());
-        } else if old_disabled.contains_key(mod_id) {
-            changes.newly_enabled.push((*m).clone());
+        } else if let Some(old_mod) = old_disabled.get(mod_id) {
+            changes.newly_enabled.push(toggled(old_mod, m));
         } else if let Some(old_mod) = old_active.get(mod_id) {
-            if m.version != old_mod.version {
-                changes.updated.push(UpdatedMod {
-                    id: mod_id.to_string(),
-                    name: m.name.clone(),
-                    old_version: old_mod.version.clone(),
-                    new_version: m.version.clone(),
-                    filename: m.filename.clone(),
-                });
+            changes.nested_updated.extend(compare_nested(old_mod, m));
+            compare_platform(old_mod, m, &mut changes);
+            let update = updated_mod(old_mod, m);
+            let suffix_only = m.version != old_mod.version
+                && normalize_version(&m.version, &options.normalization)
+                    == normalize_version(&old_mod.version, &options.normalization);
+            if suffix_only && options.list_suffix_only {
+                changes.suffix_only.push(update);
+            } else if suffix_only {
+                changes.unchanged.push((*m).clone());
+            } else if m.version != old_mod.version {
+                changes.updated.push(update);
+            } else if is_rebuilt(old_mod, m) {
+                changes.rebuilt.push((*m).clone());
             } else {
                 changes.unchanged.push((*m).clone());
             }
@@ -310,23 +2112,211 @@ pub fn compare_snapshots(old: &Snapshot, new: &Snapshot) -> Changes {
         if !new_active.contains_key(mod_id) && !new_disabled.contains_key(mod_id) {
             changes.removed.push((*m).clone());
         } else if let Some(dis) = new_disabled.get(mod_id) {
-            changes.newly_disabled.push((*dis).clone());
+            changes.newly_disabled.push(toggled(m, dis));
         }
     }
 
+    for (mod_id, m) in &new_disabled {
+        if new_active.contains_key(mod_id) {
+            continue;
+        }
+        if let Some(old_mod) = old_disabled.get(mod_id) {
+            if toggled(old_mod, m).version_changed {
+                changes.disabled_updated.push(updated_mod(old_mod, m));
+            }
+        }
+    }
+
+    changes.currently_disabled = new.disabled.clone();
+
+    match_unpaired(&mut changes);
+    options.filter.apply(&mut changes);
     changes
 }
 
+/// Two consecutive snapshots of a range and the changes between them.
+#[derive(Debug, Clone)]
+pub struct RangeStep {
+    pub from: String,
+    pub to: String,
+    pub changes: Changes,
+}
+
+/// Changes across a run of snapshots: the net result and every step.
+#[derive(Debug, Clone, Default)]
+pub struct RangeChanges {
+    /// Net changes from the first to the last snapshot. A mod added and
+    /// removed again within the range is absent, and 1.0 → 1.1 → 1.2 shows
+    /// as 1.0 → 1.2.
+    pub total: Changes,
+    pub steps: Vec<RangeStep>,
+}
+
+impl RangeChanges {
+    /// Whether the filter kept anything out of the public changelog, in
+    /// the total or in any step.
+    pub fn has_internal(&self) -> bool {
+        self.total.internal.is_some() || self.steps.iter().any(|s| s.changes.internal.is_some())
+    }
+
+    /// [`Changes::with_internal`] applied to the total and every step.
+    pub fn with_internal(&self) -> RangeChanges {
+        RangeChanges {
+            total: self.total.with_internal(),
+            steps: self
+                .steps
+                .iter()
+                .map(|s| RangeStep {
+                    from: s.from.clone(),
+                    to: s.to.clone(),
+                    changes: s.changes.with_internal(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Compare a run of snapshots, oldest first. Every snapshot lists the whole
+/// mods folder, so the net result is the diff of the two ends; the steps in
+/// between are kept for the per-step breakdown.
+pub fn compare_range(snapshots: &[&Snapshot], options: &CompareOptions) -> RangeChanges {
+    let steps = snapshots
+        .windows(2)
+        .map(|pair| RangeStep {
+            from: pair[0].timestamp.clone(),
+            to: pair[1].timestamp.clone(),
+            changes: compare_snapshots(pair[0], pair[1], options),
+        })
+        .collect();
+    let total = match (snapshots.first(), snapshots.last()) {
+        (Some(first), Some(last)) if snapshots.len() > 1 => compare_snapshots(first, last, options),
+        _ => Changes::default(),
+    };
+    RangeChanges { total, steps }
+}
+
+/// A mod active in both editions, on different versions.
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionMismatch {
+    pub id: String,
+    pub name: String,
+    pub version_a: String,
+    pub version_b: String,
+}
+
+/// How two editions of the pack (e.g. Full and Lite) differ. Only active
+/// mods count; a mod disabled in one edition is missing from it.
+#[derive(Debug, Clone, Default)]
+pub struct EditionDiff {
+    pub only_in_a: Vec<ModData>,
+    pub only_in_b: Vec<ModData>,
+    pub version_mismatch: Vec<VersionMismatch>,
+    /// Mods active in both editions, mismatched versions included.
+    pub shared: usize,
+}
+
+impl EditionDiff {
+    pub fn total_differences(&self) -> usize {
+        self.only_in_a.len() + self.only_in_b.len() + self.version_mismatch.len()
+    }
+}
+
+/// Compare the snapshots of two editions. Versions that differ only in what
+/// [`CompareOptions::normalization`] ignores count as the same, and mods
+/// the filter hides are skipped.
+pub fn compare_editions(a: &Snapshot, b: &Snapshot, options: &CompareOptions) -> EditionDiff {
+    let a_active = index_by_id(&a.active);
+    let b_active = index_by_id(&b.active);
+    let mut diff = EditionDiff::default();
+
+    let hidden = |id: &str| options.filter.action(id) == Some(FilterAction::Hide);
+    for (id, m) in a_active.iter().filter(|(id, _)| !hidden(id)) {
+        let Some(other) = b_active.get(id) else {
+            diff.only_in_a.push((*m).clone());
+            continue;
+        };
+        diff.shared += 1;
+        if normalize_version(&m.version, &options.normalization)
+            != normalize_version(&other.version, &options.normalization)
+        {
+            diff.version_mismatch.push(VersionMismatch {
+                id: id.to_string(),
+                name: m.name.clone(),
+                version_a: m.version.clone(),
+                version_b: other.version.clone(),
+            });
+        }
+    }
+    diff.only_in_b = b_active
+        .iter()
+        .filter(|(id, _)| !a_active.contains_key(*id) && !hidden(id))
+        .map(|(_, m)| (*m).clone())
+        .collect();
+    diff
+}
+
 // ──────────────────────────────────────────────────────────────────────
 // Markdown generation
 // ──────────────────────────────────────────────────────────────────────
 
+/// Optional layout choices for [`generate_markdown`].
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownOptions {
+    /// Split the updated section into major / minor / patch / other.
+    pub group_updates: bool,
+    /// Append a step-by-step breakdown to range changelogs
+    /// ([`generate_range_markdown`]).
+    pub per_step: bool,
+    /// The profile's category mapping; when set, every change kind is split
+    /// into one subsection per category.
+    pub categories: CategoryMap,
+}
+
+/// Push one bullet per item. With categories configured, the bullets are
+/// split under a `level` heading per category, uncategorized mods last.
+fn push_categorized<I>(
+    lines: &mut Vec<String>,
+    items: Vec<I>,
+    id: impl Fn(&I) -> &str,
+    line: impl Fn(&I) -> String,
+    options: &MarkdownOptions,
+    level: &str,
+    lang: Lang,
+) {
+    if options.categories.is_empty() {
+        lines.extend(items.iter().map(line));
+        return;
+    }
+    for (i, (category, group)) in options.categories.group(items, id).into_iter().enumerate() {
+        if i > 0 {
+            lines.push(String::new());
+        }
+        let category = category.unwrap_or(T::md_uncategorized(lang));
+        lines.push(T::md_category(lang, level, category, group.len()));
+        lines.extend(group.iter().map(&line));
+    }
+}
+
+/// Heading of the update group a kind is listed under. Sidegrades and
+/// incomparable versions share "other"; downgrades have their own section.
+fn update_group_label(kind: UpdateKind, lang: Lang) -> &'static str {
+    match kind {
+        UpdateKind::Major => T::update_major(lang),
+        UpdateKind::Minor => T::update_minor(lang),
+        UpdateKind::Patch => T::update_patch(lang),
+        UpdateKind::Sidegrade | UpdateKind::Incomparable | UpdateKind::Downgrade => {
+            T::update_other(lang)
+        }
+    }
+}
+
 pub fn generate_markdown(
     display_name: &str,
     changes: &Changes,
     new_snapshot: &Snapshot,
     old_snapshot: Option<&Snapshot>,
     lang: Lang,
+    options: &MarkdownOptions,
 ) -> String {
     let mut lines = Vec::new();
 
@@ -344,35 +2334,223 @@ pub fn generate_markdown(
         lines.push(format!("**{}:** {}\n", T::md_compared_with(lang), old.timestamp));
     }
 
+    if !new_snapshot.duplicates.is_empty() {
+        lines.push(T::md_duplicate_ids(lang, new_snapshot.duplicates.len()));
+        lines.push(format!("*{}*\n", T::duplicate_ids_hint(lang)));
+        for d in &new_snapshot.duplicates {
+            let files: Vec<String> = d.filenames.iter().map(|f| format!("`{f}`")).collect();
+            lines.push(T::md_duplicate_id_detail(lang, &d.id, &files.join(", ")));
+        }
+        lines.push(String::new());
+    }
+
     lines.push("\n---\n".to_string());
 
     if !changes.added.is_empty() {
         lines.push(T::md_new_mods(lang, changes.added.len()));
         let mut sorted = changes.added.clone();
-        sorted.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-        for m in &sorted {
-            lines.push(format!("* `{}` v{}", m.name, m.version));
-        }
+        sorted.sort_by_key(|a| a.name.to_lowercase());
+        push_categorized(
+            &mut lines,
+            sorted,
+            |m| &m.id,
+            |m| format!("* `{}` v{}", m.name, m.version),
+            options,
+            "###",
+            lang,
+        );
         lines.push(String::new());
     }
 
-    if !changes.updated.is_empty() {
-        lines.push(T::md_updated_mods(lang, changes.updated.len()));
-        let mut sorted = changes.updated.clone();
-        sorted.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-        for m in &sorted {
-            lines.push(T::md_updated_detail(lang, &m.name, &m.new_version, &m.old_version));
+    let (downgraded, mut upgraded): (Vec<&UpdatedMod>, Vec<&UpdatedMod>) = changes
+        .updated
+        .iter()
+        .partition(|m| m.kind == UpdateKind::Downgrade);
+
+    if !upgraded.is_empty() {
+        lines.push(T::md_updated_mods(lang, upgraded.len()));
+        upgraded.sort_by_key(|a| a.name.to_lowercase());
+        if options.group_updates {
+            let mut groups: Vec<(&str, Vec<&UpdatedMod>)> = Vec::new();
+            upgraded.sort_by_key(|a| a.kind);
+            for m in upgraded {
+                let label = update_group_label(m.kind, lang);
+                match groups.last_mut() {
+                    Some((last, items)) if *last == label => items.push(m),
+                    _ => groups.push((label, vec![m])),
+                }
+            }
+            for (label, items) in groups {
+                lines.push(T::md_update_group(lang, label, items.len()));
+                push_categorized(
+                    &mut lines,
+                    items,
+                    |m| &m.id,
+                    |m| T::md_updated_detail(lang, &m.name, &m.new_version, &m.old_version),
+                    options,
+                    "####",
+                    lang,
+                );
+                lines.push(String::new());
+            }
+        } else {
+            push_categorized(
+                &mut lines,
+                upgraded,
+                |m| &m.id,
+                |m| T::md_updated_detail(lang, &m.name, &m.new_version, &m.old_version),
+                options,
+                "###",
+                lang,
+            );
+            lines.push(String::new());
         }
+    }
+
+    if !downgraded.is_empty() {
+        lines.push(T::md_downgraded_mods(lang, downgraded.len()));
+        let mut sorted = downgraded;
+        sorted.sort_by_key(|a| a.name.to_lowercase());
+        push_categorized(
+            &mut lines,
+            sorted,
+            |m| &m.id,
+            |m| T::md_updated_detail(lang, &m.name, &m.new_version, &m.old_version),
+            options,
+            "###",
+            lang,
+        );
+        lines.push(String::new());
+    }
+
+    if !changes.suffix_only.is_empty() {
+        lines.push(T::md_suffix_only(lang, changes.suffix_only.len()));
+        let mut sorted = changes.suffix_only.clone();
+        sorted.sort_by_key(|a| a.name.to_lowercase());
+        push_categorized(
+            &mut lines,
+            sorted,
+            |m| &m.id,
+            |m| T::md_updated_detail(lang, &m.name, &m.new_version, &m.old_version),
+            options,
+            "###",
+            lang,
+        );
+        lines.push(String::new());
+    }
+
+    if !changes.rebuilt.is_empty() {
+        lines.push(T::md_rebuilt_mods(lang, changes.rebuilt.len()));
+        let mut sorted = changes.rebuilt.clone();
+        sorted.sort_by_key(|a| a.name.to_lowercase());
+        push_categorized(
+            &mut lines,
+            sorted,
+            |m| &m.id,
+            |m| format!("* `{}` v{}", m.name, m.version),
+            options,
+            "###",
+            lang,
+        );
+        lines.push(String::new());
+    }
+
+    if !changes.replaced.is_empty() {
+        lines.push(T::md_replaced_mods(lang, changes.replaced.len()));
+        let mut sorted = changes.replaced.clone();
+        sorted.sort_by_key(|r| r.old.name.to_lowercase());
+        push_categorized(
+            &mut lines,
+            sorted,
+            |r| &r.new.id,
+            |r| {
+                T::md_replaced_detail(
+                    lang,
+                    &r.old.name,
+                    &r.old.version,
+                    &r.new.name,
+                    &r.new.version,
+                    r.matched_by.label(lang),
+                )
+            },
+            options,
+            "###",
+            lang,
+        );
+        lines.push(String::new());
+    }
+
+    let platform = changes.loader_changed.len() + changes.environment_changed.len();
+    if platform > 0 {
+        lines.push(T::md_platform_changed(lang, platform));
+        let mut entries: Vec<(String, String, &str)> = changes
+            .loader_changed
+            .iter()
+            .map(|c| {
+                let line = T::md_loader_changed_detail(lang, &c.name, &c.old_loader, &c.new_loader);
+                (c.name.to_lowercase(), line, c.id.as_str())
+            })
+            .chain(changes.environment_changed.iter().map(|c| {
+                let line = T::md_environment_changed_detail(
+                    lang,
+                    &c.name,
+                    c.old_environment.label(lang),
+                    c.new_environment.label(lang),
+                );
+                (c.name.to_lowercase(), line, c.id.as_str())
+            }))
+            .collect();
+        entries.sort();
+        push_categorized(
+            &mut lines,
+            entries,
+            |(_, _, id)| id,
+            |(_, line, _)| line.clone(),
+            options,
+            "###",
+            lang,
+        );
+        lines.push(String::new());
+    }
+
+    if !changes.nested_updated.is_empty() {
+        lines.push(T::md_nested_updated(lang, changes.nested_updated.len()));
+        let mut sorted = changes.nested_updated.clone();
+        sorted.sort_by_key(|a| (a.host_name.to_lowercase(), a.name.to_lowercase()));
+        // Bundled mods go with their host's category.
+        push_categorized(
+            &mut lines,
+            sorted,
+            |m| &m.host_id,
+            |m| {
+                T::md_nested_updated_detail(
+                    lang,
+                    &m.host_name,
+                    &m.name,
+                    &m.new_version,
+                    &m.old_version,
+                )
+            },
+            options,
+            "###",
+            lang,
+        );
         lines.push(String::new());
     }
 
     if !changes.removed.is_empty() {
         lines.push(T::md_removed_mods(lang, changes.removed.len()));
         let mut sorted = changes.removed.clone();
-        sorted.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-        for m in &sorted {
-            lines.push(format!("* `{}` v{}", m.name, m.version));
-        }
+        sorted.sort_by_key(|a| a.name.to_lowercase());
+        push_categorized(
+            &mut lines,
+            sorted,
+            |m| &m.id,
+            |m| format!("* `{}` v{}", m.name, m.version),
+            options,
+            "###",
+            lang,
+        );
         lines.push(String::new());
     }
 
@@ -380,30 +2558,95 @@ pub fn generate_markdown(
         lines.push(T::md_newly_disabled(lang, changes.newly_disabled.len()));
         lines.push(T::md_disabled_reason(lang).to_string());
         let mut sorted = changes.newly_disabled.clone();
-        sorted.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-        for m in &sorted {
-            lines.push(format!("* `{}` v{}", m.name, m.version));
-        }
+        sorted.sort_by_key(|t| t.new.name.to_lowercase());
+        push_categorized(
+            &mut lines,
+            sorted,
+            |t| &t.new.id,
+            |t| {
+                if t.version_changed {
+                    T::md_disabled_detail(lang, &t.new.name, &t.new.version, &t.old.version)
+                } else {
+                    format!("* `{}` v{}", t.new.name, t.new.version)
+                }
+            },
+            options,
+            "###",
+            lang,
+        );
         lines.push(String::new());
     }
 
     if !changes.newly_enabled.is_empty() {
         lines.push(T::md_newly_enabled(lang, changes.newly_enabled.len()));
         let mut sorted = changes.newly_enabled.clone();
-        sorted.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        sorted.sort_by_key(|t| t.new.name.to_lowercase());
+        push_categorized(
+            &mut lines,
+            sorted,
+            |t| &t.new.id,
+            |t| {
+                if t.version_changed {
+                    T::md_reenabled_detail(lang, &t.new.name, &t.new.version, &t.old.version)
+                } else {
+                    format!("* `{}` v{}", t.new.name, t.new.version)
+                }
+            },
+            options,
+            "###",
+            lang,
+        );
+        lines.push(String::new());
+    }
+
+    // Collapsed on GitHub; other renderers just show the list.
+    if let Some(libraries) = &changes.libraries {
+        let library_lines = compact_change_lines(libraries, lang);
+        if !library_lines.is_empty() {
+            lines.push("<details>".to_string());
+            lines.push(format!(
+                "<summary>{}</summary>\n",
+                T::md_libraries(lang, libraries.total_changes())
+            ));
+            lines.extend(library_lines);
+            lines.push("\n</details>\n".to_string());
+        }
+    }
+
+    if !changes.currently_disabled.is_empty() {
+        lines.push("---\n".to_string());
+        lines.push(T::md_currently_disabled(lang, changes.currently_disabled.len()));
+        let mut sorted = changes.currently_disabled.clone();
+        sorted.sort_by_key(|a| a.name.to_lowercase());
         for m in &sorted {
             lines.push(format!("* `{}` v{}", m.name, m.version));
         }
         lines.push(String::new());
+
+        if !changes.disabled_updated.is_empty() {
+            lines.push(T::md_disabled_updated(lang, changes.disabled_updated.len()));
+            let mut sorted = changes.disabled_updated.clone();
+            sorted.sort_by_key(|a| a.name.to_lowercase());
+            push_categorized(
+                &mut lines,
+                sorted,
+                |m| &m.id,
+                |m| T::md_updated_detail(lang, &m.name, &m.new_version, &m.old_version),
+                options,
+                "####",
+                lang,
+            );
+            lines.push(String::new());
+        }
     }
 
-    if !new_snapshot.disabled.is_empty() {
+    let doubtful = low_confidence_mods(new_snapshot);
+    if !doubtful.is_empty() {
         lines.push("---\n".to_string());
-        lines.push(T::md_currently_disabled(lang, new_snapshot.disabled.len()));
-        let mut sorted = new_snapshot.disabled.clone();
-        sorted.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-        for m in &sorted {
-            lines.push(format!("* `{}` v{}", m.name, m.version));
+        lines.push(T::md_check_these(lang, doubtful.len()));
+        for m in doubtful {
+            let doubts = m.doubts(lang).join(", ");
+            lines.push(T::md_check_these_detail(lang, &m.name, &m.filename, &doubts));
         }
         lines.push(String::new());
     }
@@ -411,12 +2654,16 @@ pub fn generate_markdown(
     if !new_snapshot.failed.is_empty() {
         lines.push("---\n".to_string());
         lines.push(T::md_read_errors(lang, new_snapshot.failed.len()));
-        let mut sorted = new_snapshot.failed.clone();
-        sorted.sort();
-        for f in &sorted {
-            lines.push(T::md_read_error_detail(lang, f));
+        for (reason, files) in group_failures(&new_snapshot.failed) {
+            lines.push(T::md_read_error_group(lang, reason.label(lang), files.len()));
+            for f in files {
+                lines.push(match &f.detail {
+                    Some(detail) => T::md_read_error_detail_with(lang, &f.filename, detail),
+                    None => T::md_read_error_detail(lang, &f.filename),
+                });
+            }
+            lines.push(String::new());
         }
-        lines.push(String::new());
     }
 
     lines.push("---\n".to_string());
@@ -426,15 +2673,312 @@ pub fn generate_markdown(
     lines.join("\n")
 }
 
+/// Changelog for a run of snapshots (oldest first): the net changes as in
+/// [`generate_markdown`], optionally followed by what each step changed.
+pub fn generate_range_markdown(
+    display_name: &str,
+    range: &RangeChanges,
+    snapshots: &[&Snapshot],
+    lang: Lang,
+    options: &MarkdownOptions,
+) -> String {
+    let (Some(first), Some(last)) = (snapshots.first(), snapshots.last()) else {
+        return String::new();
+    };
+    let mut md = generate_markdown(display_name, &range.total, last, Some(first), lang, options);
+    if !options.per_step || range.steps.is_empty() {
+        return md;
+    }
+
+    let mut lines = vec![String::new(), "---\n".to_string()];
+    lines.push(T::md_range_steps(lang, range.steps.len()));
+    for (i, step) in range.steps.iter().enumerate() {
+        lines.push(T::md_range_step(
+            lang,
+            i + 1,
+            &short_timestamp(&step.from),
+            &short_timestamp(&step.to),
+            step.changes.total_changes(),
+        ));
+        // Steps are already terse, so library changes are listed inline.
+        let mut step_lines = compact_change_lines(&step.changes, lang);
+        if let Some(libraries) = &step.changes.libraries {
+            step_lines.extend(compact_change_lines(libraries, lang));
+        }
+        if step_lines.is_empty() {
+            lines.push(T::md_step_no_changes(lang).to_string());
+        }
+        lines.extend(step_lines);
+        lines.push(String::new());
+    }
+    md.push_str(&lines.join("\n"));
+    md
+}
+
+/// One line per change in every list of `changes` (libraries aside), for the
+/// per-step breakdown and the libraries section, so the line count matches
+/// [`Changes::total_changes`].
+fn compact_change_lines(changes: &Changes, lang: Lang) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    let mut added: Vec<&ModData> = changes.added.iter().collect();
+    added.sort_by_key(|m| m.name.to_lowercase());
+    lines.extend(added.iter().map(|m| T::md_step_added(lang, &m.name, &m.version)));
+
+    let mut updated: Vec<&UpdatedMod> = changes.updated.iter().collect();
+    updated.sort_by_key(|m| m.name.to_lowercase());
+    lines.extend(
+        updated
+            .iter()
+            .map(|m| T::md_step_updated(lang, &m.name, &m.new_version, &m.old_version)),
+    );
+
+    let mut suffix_only: Vec<&UpdatedMod> = changes.suffix_only.iter().collect();
+    suffix_only.sort_by_key(|m| m.name.to_lowercase());
+    lines.extend(
+        suffix_only
+            .iter()
+            .map(|m| T::md_step_suffix_only(lang, &m.name, &m.new_version, &m.old_version)),
+    );
+
+    let mut removed: Vec<&ModData> = changes.removed.iter().collect();
+    removed.sort_by_key(|m| m.name.to_lowercase());
+    lines.extend(removed.iter().map(|m| T::md_step_removed(lang, &m.name, &m.version)));
+
+    let mut replaced: Vec<&ReplacedMod> = changes.replaced.iter().collect();
+    replaced.sort_by_key(|r| r.old.name.to_lowercase());
+    lines.extend(
+        replaced
+            .iter()
+            .map(|r| T::md_step_replaced(lang, &r.old.name, &r.new.name, &r.new.version)),
+    );
+
+    let mut rebuilt: Vec<&ModData> = changes.rebuilt.iter().collect();
+    rebuilt.sort_by_key(|m| m.name.to_lowercase());
+    lines.extend(rebuilt.iter().map(|m| T::md_step_rebuilt(lang, &m.name, &m.version)));
+
+    let mut platform: Vec<(String, String)> = changes
+        .loader_changed
+        .iter()
+        .map(|c| {
+            let line = T::md_step_loader(lang, &c.name, &c.old_loader, &c.new_loader);
+            (c.name.to_lowercase(), line)
+        })
+        .chain(changes.environment_changed.iter().map(|c| {
+            let line = T::md_step_environment(
+                lang,
+                &c.name,
+                c.old_environment.label(lang),
+                c.new_environment.label(lang),
+            );
+            (c.name.to_lowercase(), line)
+        }))
+        .collect();
+    platform.sort();
+    lines.extend(platform.into_iter().map(|(_, line)| line));
+
+    let mut enabled: Vec<&ToggledMod> = changes.newly_enabled.iter().collect();
+    enabled.sort_by_key(|t| t.new.name.to_lowercase());
+    lines.extend(enabled.iter().map(|t| T::md_step_enabled(lang, &t.new.name, &t.new.version)));
+
+    let mut disabled: Vec<&ToggledMod> = changes.newly_disabled.iter().collect();
+    disabled.sort_by_key(|t| t.new.name.to_lowercase());
+    lines.extend(disabled.iter().map(|t| T::md_step_disabled(lang, &t.new.name, &t.new.version)));
+
+    let mut disabled_updated: Vec<&UpdatedMod> = changes.disabled_updated.iter().collect();
+    disabled_updated.sort_by_key(|m| m.name.to_lowercase());
+    lines.extend(disabled_updated.iter().map(|m| {
+        T::md_step_disabled_updated(lang, &m.name, &m.new_version, &m.old_version)
+    }));
+
+    let mut nested: Vec<&NestedUpdate> = changes.nested_updated.iter().collect();
+    nested.sort_by_key(|n| (n.host_name.to_lowercase(), n.name.to_lowercase()));
+    lines.extend(nested.iter().map(|n| {
+        T::md_step_nested(lang, &n.host_name, &n.name, &n.new_version, &n.old_version)
+    }));
+
+    lines
+}
+
+/// Report of how two editions differ, with headings naming the edition
+/// that has each mod rather than calling them added or removed.
+pub fn generate_edition_markdown(
+    base_name: &str,
+    (edition_a, snapshot_a): (&str, &Snapshot),
+    (edition_b, snapshot_b): (&str, &Snapshot),
+    diff: &EditionDiff,
+    lang: Lang,
+) -> String {
+    let mut lines = Vec::new();
+
+    lines.push(T::md_edition_heading(lang, base_name, edition_a, edition_b));
+    lines.push(format!(
+        "**{}:** {}\n",
+        T::md_date(lang),
+        chrono::Local::now().format("%d.%m.%Y %H:%M")
+    ));
+    lines.push(format!("**{}:** {}\n", edition_a, snapshot_a.timestamp));
+    lines.push(format!("**{}:** {}\n", edition_b, snapshot_b.timestamp));
+    lines.push("\n---\n".to_string());
+
+    for (edition, only) in [(edition_a, &diff.only_in_a), (edition_b, &diff.only_in_b)] {
+        if only.is_empty() {
+            continue;
+        }
+        lines.push(T::md_edition_only_in(lang, edition, only.len()));
+        let mut sorted = only.clone();
+        sorted.sort_by_key(|a| a.name.to_lowercase());
+        for m in &sorted {
+            lines.push(format!("* `{}` v{}", m.name, m.version));
+        }
+        lines.push(String::new());
+    }
+
+    if !diff.version_mismatch.is_empty() {
+        lines.push(T::md_edition_version_diff(lang, diff.version_mismatch.len()));
+        let mut sorted = diff.version_mismatch.clone();
+        sorted.sort_by_key(|a| a.name.to_lowercase());
+        for m in &sorted {
+            lines.push(T::md_edition_version_detail(
+                lang,
+                &m.name,
+                edition_a,
+                &m.version_a,
+                edition_b,
+                &m.version_b,
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    if diff.total_differences() == 0 {
+        lines.push(T::edition_same(lang).to_string());
+        lines.push(String::new());
+    }
+
+    lines.push("---\n".to_string());
+    lines.push(T::md_edition_summary(lang, diff.shared, diff.total_differences()));
+
+    lines.join("\n")
+}
+
+/// Changelog for admins mirroring the pack on a dedicated server: only the
+/// additions, removals and updates of mods that run there (see
+/// [`Environment::runs_on_server`]). Re-enabled mods and the new side of a
+/// replacement count as additions, disabled mods and the old side as
+/// removals, and a mod whose environment crossed the client-only line is
+/// added to or removed from the server.
+pub fn generate_server_markdown(
+    display_name: &str,
+    changes: &Changes,
+    new_snapshot: &Snapshot,
+    old_snapshot: Option<&Snapshot>,
+    lang: Lang,
+) -> String {
+    let new_active = index_by_id(&new_snapshot.active);
+    let old_active = old_snapshot.map(|s| index_by_id(&s.active)).unwrap_or_default();
+
+    let mut added: Vec<&ModData> = changes
+        .added
+        .iter()
+        .chain(changes.newly_enabled.iter().map(|t| &t.new))
+        .chain(changes.replaced.iter().map(|r| &r.new))
+        .filter(|m| m.environment.runs_on_server())
+        .collect();
+    let mut removed: Vec<&ModData> = changes
+        .removed
+        .iter()
+        .chain(changes.newly_disabled.iter().map(|t| &t.old))
+        .chain(changes.replaced.iter().map(|r| &r.old))
+        .filter(|m| m.environment.runs_on_server())
+        .collect();
+
+    let mut crossed = HashSet::new();
+    for c in &changes.environment_changed {
+        match (c.old_environment.runs_on_server(), c.new_environment.runs_on_server()) {
+            (false, true) => added.extend(new_active.get(c.id.as_str())),
+            (true, false) => removed.extend(old_active.get(c.id.as_str())),
+            _ => continue,
+        }
+        crossed.insert(c.id.as_str());
+    }
+
+    let mut updated: Vec<&UpdatedMod> = changes
+        .updated
+        .iter()
+        .filter(|u| !crossed.contains(u.id.as_str()))
+        .filter(|u| {
+            new_active
+                .get(u.id.as_str())
+                .is_some_and(|m| m.environment.runs_on_server())
+        })
+        .collect();
+
+    let mut lines = Vec::new();
+    lines.push(T::md_server_heading(lang, display_name));
+    lines.push(format!(
+        "**{}:** {}\n",
+        T::md_date(lang),
+        chrono::Local::now().format("%d.%m.%Y %H:%M")
+    ));
+    if let Some(old) = old_snapshot {
+        lines.push(format!("**{}:** {}\n", T::md_compared_with(lang), old.timestamp));
+    }
+    lines.push(T::md_server_note(lang).to_string());
+    lines.push("\n---\n".to_string());
+
+    if !added.is_empty() {
+        lines.push(T::md_new_mods(lang, added.len()));
+        added.sort_by_key(|a| a.name.to_lowercase());
+        for m in &added {
+            lines.push(format!("* `{}` v{}", m.name, m.version));
+        }
+        lines.push(String::new());
+    }
+
+    if !updated.is_empty() {
+        lines.push(T::md_updated_mods(lang, updated.len()));
+        updated.sort_by_key(|a| a.name.to_lowercase());
+        for m in &updated {
+            lines.push(T::md_updated_detail(lang, &m.name, &m.new_version, &m.old_version));
+        }
+        lines.push(String::new());
+    }
+
+    if !removed.is_empty() {
+        lines.push(T::md_removed_mods(lang, removed.len()));
+        removed.sort_by_key(|a| a.name.to_lowercase());
+        for m in &removed {
+            lines.push(format!("* `{}` v{}", m.name, m.version));
+        }
+        lines.push(String::new());
+    }
+
+    let total = added.len() + updated.len() + removed.len();
+    if total == 0 {
+        lines.push(T::md_server_no_changes(lang).to_string());
+        lines.push(String::new());
+    }
+
+    lines.push("---\n".to_string());
+    lines.push(T::md_server_summary(lang, total));
+
+    lines.join("\n")
+}
+
 // ──────────────────────────────────────────────────────────────────────
 // Helpers
 // ──────────────────────────────────────────────────────────────────────
 
+static WHITESPACE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s+").unwrap());
+static SLUG_INVALID_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[^a-z0-9._\-]").unwrap());
+static REPEATED_DASH_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"-{2,}").unwrap());
+
 pub fn slugify(s: &str) -> String {
     let s = s.trim().to_lowercase();
-    let s = Regex::new(r"\s+").unwrap().replace_all(&s, "-");
-    let s = Regex::new(r"[^a-z0-9._\-]").unwrap().replace_all(&s, "");
-    let s = Regex::new(r"-{2,}").unwrap().replace_all(&s, "-");
+    let s = WHITESPACE_RE.replace_all(&s, "-");
+    let s = SLUG_INVALID_RE.replace_all(&s, "");
+    let s = REPEATED_DASH_RE.replace_all(&s, "-");
     if s.is_empty() {
         "pack".to_string()
     } else {
@@ -499,3 +3043,8 @@ pub fn build_timestamped_prefix(base_name: &str, edition: &str, pack_version: &s
 pub fn edition_slug(edition: &str) -> String {
     slugify(normalize_edition(edition))
 }
+
+/// RFC 3339 timestamp cut to minutes for headings ("2026-03-01 14:05").
+pub fn short_timestamp(ts: &str) -> String {
+    ts.chars().take(16).collect::<String>().replace('T', " ")
+}
@@ -0,0 +1,603 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// One dot-separated component of a version string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Num(u64),
+    Text(String),
+}
+
+impl PartialOrd for Part {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Part {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Part::Num(a), Part::Num(b)) => a.cmp(b),
+            // Numbers sort after text, so "1.0.0" > "1.0.beta".
+            (Part::Num(_), Part::Text(_)) => Ordering::Greater,
+            (Part::Text(_), Part::Num(_)) => Ordering::Less,
+            (Part::Text(a), Part::Text(b)) => a.cmp(b),
+        }
+    }
+}
+
+/// A version split into release components, an optional pre-release tag and
+/// a numeric build counter (`1.0.0-2`) that only breaks ties. Build metadata
+/// after `+` is dropped, as SemVer and Fabric both ignore it for ordering.
+#[derive(Debug, Clone)]
+struct Parsed {
+    release: Vec<Part>,
+    pre: Option<Vec<Part>>,
+    build: Vec<Part>,
+}
+
+fn parse_parts(s: &str) -> Vec<Part> {
+    s.split('.')
+        .map(|p| match p.parse::<u64>() {
+            Ok(n) => Part::Num(n),
+            Err(_) => Part::Text(p.to_lowercase()),
+        })
+        .collect()
+}
+
+fn parse(v: &str) -> Option<Parsed> {
+    let v = v.trim();
+    let v = v.strip_prefix(['v', 'V']).unwrap_or(v);
+    let v = strip_mc_prefix(v);
+    let v = v.split('+').next().unwrap_or(v);
+
+    // Loader names never order versions: 1.2.3-fabric is 1.2.3-forge.
+    let mut tokens: Vec<&str> = v.split('-').filter(|t| !is_loader(t)).collect();
+    if has_leading_mc_version(&tokens) {
+        tokens.remove(0);
+    }
+    let (release, rest) = tokens.split_first()?;
+    if !release.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    // After the release, a Minecraft version is decoration and a plain
+    // number is a build counter; the first other token starts the
+    // pre-release tag, which runs to the end.
+    let mut pre: Option<Vec<&str>> = None;
+    let mut build = Vec::new();
+    for token in rest {
+        let lower = token.to_lowercase();
+        if is_mc_version(lower.strip_prefix("mc").unwrap_or(&lower)) {
+            continue;
+        }
+        match &mut pre {
+            Some(pre) => pre.push(token),
+            None if is_numeric(token) => build.push(*token),
+            None => pre = Some(vec![token]),
+        }
+    }
+    Some(Parsed {
+        release: parse_parts(release),
+        pre: pre.map(|p| parse_parts(&p.join("."))),
+        build: parse_parts(&build.join(".")),
+    })
+}
+
+/// Drop a leading Minecraft version as in `mc1.20-0.5.3`, leaving `0.5.3`.
+fn strip_mc_prefix(v: &str) -> &str {
+    let Some(rest) = v.strip_prefix("mc").or_else(|| v.strip_prefix("MC")) else {
+        return v;
+    };
+    if !rest.starts_with(|c: char| c.is_ascii_digit()) {
+        return v;
+    }
+    match rest.split_once(['-', '_']) {
+        Some((_, mod_version)) if !mod_version.is_empty() => mod_version,
+        _ => v,
+    }
+}
+
+fn cmp_parts(a: &[Part], b: &[Part]) -> Ordering {
+    // Missing trailing components count as zero: 1.2 == 1.2.0.
+    let len = a.len().max(b.len());
+    for i in 0..len {
+        let x = a.get(i).cloned().unwrap_or(Part::Num(0));
+        let y = b.get(i).cloned().unwrap_or(Part::Num(0));
+        match x.cmp(&y) {
+            Ordering::Equal => continue,
+            other => return other,
+        }
+    }
+    Ordering::Equal
+}
+
+fn cmp_parsed(a: &Parsed, b: &Parsed) -> Ordering {
+    cmp_parts(&a.release, &b.release)
+        .then_with(|| match (&a.pre, &b.pre) {
+            (None, None) => Ordering::Equal,
+            // A pre-release sorts before the release it precedes.
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(x), Some(y)) => cmp_parts(x, y),
+        })
+        .then_with(|| cmp_parts(&a.build, &b.build))
+}
+
+/// Decorations ignored when deciding whether a version really changed, so
+/// `3.1.0+1.21-fabric` -> `3.1.0+1.21.1-fabric` is not an update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Normalization {
+    /// Everything after `+`, apart from what the other switches keep.
+    pub build_metadata: bool,
+    /// Minecraft versions: `mc1.20.1` anywhere, a bare `1.20.1` after the
+    /// mod version, or before it as in `1.20.1-0.5.3`.
+    pub mc_version: bool,
+    /// Loader names: `fabric`, `forge`, `neoforge`, `quilt`.
+    pub loader: bool,
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Normalization {
+            build_metadata: true,
+            mc_version: true,
+            loader: true,
+        }
+    }
+}
+
+const LOADER_TOKENS: [&str; 4] = ["fabric", "forge", "neoforge", "quilt"];
+
+fn is_loader(token: &str) -> bool {
+    LOADER_TOKENS.contains(&token.to_lowercase().as_str())
+}
+
+/// Digits and dots only, as in `2` or `15.2.0`.
+fn is_numeric(token: &str) -> bool {
+    !token.is_empty() && token.split('.').all(|p| p.parse::<u64>().is_ok())
+}
+
+/// Whether `tokens` (a version split at `-`) start with a Minecraft version
+/// placed before the mod version, as in `1.20.1-1.0.0`. Only when the next
+/// token is itself a dotted number: in `1.12.0-2` or `1.19.2-rc1` the
+/// leading token is the mod version.
+fn has_leading_mc_version(tokens: &[&str]) -> bool {
+    match tokens {
+        [first, next, ..] => is_mc_version(first) && next.contains('.') && is_numeric(next),
+        _ => false,
+    }
+}
+
+/// `1.7` through `1.99`, optionally with a patch number.
+fn is_mc_version(token: &str) -> bool {
+    let mut parts = token.split('.');
+    let (Some("1"), Some(minor)) = (parts.next(), parts.next()) else {
+        return false;
+    };
+    let minor_ok = minor.parse::<u32>().is_ok_and(|m| (7..100).contains(&m));
+    let rest: Vec<&str> = parts.collect();
+    minor_ok && rest.len() <= 1 && rest.iter().all(|p| p.parse::<u32>().is_ok())
+}
+
+/// The version with the decorations selected in `n` removed. A version that
+/// would be left without a numeric core (it *is* a Minecraft version) is
+/// returned unchanged, so it is still compared as written.
+pub fn normalize_version(version: &str, n: &Normalization) -> String {
+    let version = version.trim();
+    let (main, build) = match version.split_once('+') {
+        Some((main, build)) => (main, Some(build)),
+        None => (version, None),
+    };
+
+    let main_tokens: Vec<&str> = main.split(['-', '_']).collect();
+    // A leading bare MC version only counts as one when a dotted mod version
+    // follows; in 1.12.0-2 or 1.19.2-rc1 it is the mod version itself.
+    let core: Vec<&str> = main_tokens
+        .iter()
+        .copied()
+        .filter(|t| !is_loader(t))
+        .collect();
+    let leading_mc = has_leading_mc_version(&core);
+    let kept: Vec<&str> = main_tokens
+        .iter()
+        .enumerate()
+        .filter(|(i, token)| {
+            let lower = token.to_lowercase();
+            if n.loader && LOADER_TOKENS.contains(&lower.as_str()) {
+                return false;
+            }
+            if n.mc_version {
+                if lower.strip_prefix("mc").is_some_and(is_mc_version) {
+                    return false;
+                }
+                if is_mc_version(&lower) && (*i > 0 || leading_mc) {
+                    return false;
+                }
+            }
+            true
+        })
+        .map(|(_, token)| *token)
+        .collect();
+    if !kept
+        .iter()
+        .any(|t| t.starts_with(|c: char| c.is_ascii_digit()))
+    {
+        return version.to_string();
+    }
+
+    let mut out = kept.join("-");
+    if let (Some(build), false) = (build, n.build_metadata) {
+        let build: Vec<&str> = build
+            .split(['-', '_'])
+            .filter(|token| {
+                let lower = token.to_lowercase();
+                let loader = n.loader && LOADER_TOKENS.contains(&lower.as_str());
+                let mc = n.mc_version && is_mc_version(lower.strip_prefix("mc").unwrap_or(&lower));
+                !loader && !mc
+            })
+            .collect();
+        if !build.is_empty() {
+            out.push('+');
+            out.push_str(&build.join("-"));
+        }
+    }
+    out
+}
+
+/// How big a step an update from one version to another is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateKind {
+    /// The first version component went up.
+    Major,
+    /// The second component went up.
+    Minor,
+    /// A later component or the pre-release tag went up.
+    Patch,
+    /// Different strings, same version (e.g. only build metadata changed).
+    Sidegrade,
+    /// The new version is older.
+    Downgrade,
+    /// At least one side can't be parsed as a version.
+    Incomparable,
+}
+
+/// Classify the step from `old` to `new`. Understands SemVer, Fabric's
+/// extended format (any number of components, build metadata ignored) and
+/// the usual mod schemes such as `1.2.3+mc1.20.1` and `mc1.20-0.5.3`.
+pub fn classify_update(old: &str, new: &str) -> UpdateKind {
+    let (Some(o), Some(n)) = (parse(old), parse(new)) else {
+        return UpdateKind::Incomparable;
+    };
+    match cmp_parsed(&n, &o) {
+        Ordering::Less => UpdateKind::Downgrade,
+        Ordering::Equal => UpdateKind::Sidegrade,
+        Ordering::Greater => {
+            let len = o.release.len().max(n.release.len());
+            let first_diff = (0..len).find(|&i| {
+                o.release.get(i).cloned().unwrap_or(Part::Num(0))
+                    != n.release.get(i).cloned().unwrap_or(Part::Num(0))
+            });
+            match first_diff {
+                Some(0) => UpdateKind::Major,
+                Some(1) => UpdateKind::Minor,
+                _ => UpdateKind::Patch,
+            }
+        }
+    }
+}
+
+/// Whether `version` satisfies a dependency range. Understands Fabric/SemVer
+/// ranges (`>=1.2`, `<2`, `^1.2`, `~1.2.3`, `1.20.x`, space-separated terms
+/// that must all hold, `||` alternatives) and Maven ranges as used by Forge
+/// (`[1.0,2.0)`, `[47,)`). Returns `None` when the range or the version can't
+/// be interpreted.
+pub fn matches_range(version: &str, range: &str) -> Option<bool> {
+    let range = range.trim();
+    if range.is_empty() || range == "*" {
+        return Some(true);
+    }
+    if range.starts_with('[') || range.starts_with('(') {
+        return matches_maven_range(version, range);
+    }
+
+    let mut any_understood = false;
+    for alternative in range.split("||") {
+        let mut all = Some(true);
+        for term in alternative.split_whitespace() {
+            all = match (all, matches_term(version, term)) {
+                (Some(true), r) => r,
+                (done, _) => done,
+            };
+        }
+        match all {
+            Some(true) => return Some(true),
+            Some(false) => any_understood = true,
+            None => {}
+        }
+    }
+    any_understood.then_some(false)
+}
+
+fn matches_term(version: &str, term: &str) -> Option<bool> {
+    if term == "*" {
+        return Some(true);
+    }
+    let ops = [">=", "<=", ">", "<", "=", "^", "~"];
+    let (op, target) = ops
+        .iter()
+        .find_map(|op| term.strip_prefix(op).map(|rest| (*op, rest.trim())))
+        .unwrap_or(("", term));
+
+    let v = parse(version)?;
+
+    // Wildcards like 1.20.x match every version with that prefix.
+    let trimmed = target
+        .trim_end_matches(".x")
+        .trim_end_matches(".X")
+        .trim_end_matches(".*");
+    if op.is_empty() && trimmed != target {
+        let prefix = parse(trimmed)?;
+        return Some(
+            v.release.len() >= prefix.release.len()
+                && v.release[..prefix.release.len()] == prefix.release[..],
+        );
+    }
+
+    let t = parse(target)?;
+    let ord = cmp_parsed(&v, &t);
+    Some(match op {
+        ">=" => ord != Ordering::Less,
+        "<=" => ord != Ordering::Greater,
+        ">" => ord == Ordering::Greater,
+        "<" => ord == Ordering::Less,
+        "^" | "~" => {
+            // ^ keeps the major version, ~ keeps major and minor.
+            let keep = if op == "^" { 1 } else { 2 };
+            let same_prefix = (0..keep.min(t.release.len()))
+                .all(|i| v.release.get(i) == t.release.get(i));
+            ord != Ordering::Less && same_prefix
+        }
+        _ => ord == Ordering::Equal,
+    })
+}
+
+/// Maven version ranges: one or more comma-joined sets such as `[1.0,2.0)`,
+/// `(,1.5]`, `[1.2,)` or the exact pin `[1.2]`.
+fn matches_maven_range(version: &str, range: &str) -> Option<bool> {
+    let v = parse(version)?;
+    let mut rest = range.trim();
+    let mut understood = false;
+
+    while !rest.is_empty() {
+        let open = rest.chars().next()?;
+        if open != '[' && open != '(' {
+            return None;
+        }
+        let close_at = rest.find([']', ')'])?;
+        if close_at == 0 {
+            return None;
+        }
+        let close = rest[close_at..].chars().next()?;
+        let body = &rest[open.len_utf8()..close_at];
+        rest = rest[close_at + 1..].trim_start_matches(',').trim();
+        understood = true;
+
+        let hit = match body.split_once(',') {
+            None => cmp_parsed(&v, &parse(body)?) == Ordering::Equal,
+            Some((low, high)) => {
+                let low_ok = match low.trim() {
+                    "" => true,
+                    l => {
+                        let ord = cmp_parsed(&v, &parse(l)?);
+                        if open == '[' {
+                            ord != Ordering::Less
+                        } else {
+                            ord == Ordering::Greater
+                        }
+                    }
+                };
+                let high_ok = match high.trim() {
+                    "" => true,
+                    h => {
+                        let ord = cmp_parsed(&v, &parse(h)?);
+                        if close == ']' {
+                            ord != Ordering::Greater
+                        } else {
+                            ord == Ordering::Less
+                        }
+                    }
+                };
+                low_ok && high_ok
+            }
+        };
+        if hit {
+            return Some(true);
+        }
+    }
+
+    understood.then_some(false)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn classify_update_table() {
+        use super::UpdateKind::*;
+        let cases = [
+            ("1.0.0", "2.0.0", Major),
+            ("1.0.0", "1.1.0", Minor),
+            ("1.0.0", "1.0.1", Patch),
+            ("1.0.0", "1.0.0.1", Patch),
+            ("1.0.0-beta.1", "1.0.0-beta.2", Patch),
+            ("1.0.0-rc1", "1.0.0", Patch),
+            ("1.0.0", "1.0.0-rc1", Downgrade),
+            ("1.0.0-1", "1.0.0-2", Patch),
+            ("1.0.0", "1.0.0-1", Patch),
+            ("1.0.0+build.1", "1.0.0+build.2", Sidegrade),
+            ("2.0.0", "1.9.9", Downgrade),
+            ("v1.2.3", "v1.3.0", Minor),
+            // Minecraft version in front of the mod version.
+            ("1.20.1-1.0.0", "1.20.1-2.0.0", Major),
+            ("1.20.1-1.0.0", "1.20.1-1.0.1", Patch),
+            ("1.20.1-forge-15.2.0", "1.20.1-forge-15.3.0", Minor),
+            ("mc1.20-0.5.3", "mc1.20-0.6.0", Minor),
+            ("1.20.1-1.0.0", "1.21-1.0.0", Sidegrade),
+            // Minecraft version after the mod version.
+            ("1.2.3+mc1.20.1", "1.2.4+mc1.20.1", Patch),
+            ("3.1.0-1.20.1", "3.1.0-1.21", Sidegrade),
+            ("3.1.0-mc1.20.1", "3.2.0-mc1.20.1", Minor),
+            // A leading MC-like token that is really the mod version.
+            ("1.12.0-2", "1.12.1-2", Patch),
+            ("1.19.2-rc1", "1.19.2", Patch),
+            // Loader names are not pre-releases.
+            ("1.2.3-fabric", "1.2.3-forge", Sidegrade),
+            ("1.2.3-fabric", "1.2.4-fabric", Patch),
+            ("1.2.3", "1.2.3-neoforge", Sidegrade),
+            ("abc", "1.0.0", Incomparable),
+            ("1.0.0", "", Incomparable),
+        ];
+        for (old, new, expected) in cases {
+            assert_eq!(super::classify_update(old, new), expected, "{old} -> {new}");
+        }
+    }
+
+    #[test]
+    fn normalize_keeps_mod_versions() {
+        let all = super::Normalization::default();
+        let cases = [
+            ("1.20.1-1.0.0", "1.0.0"),
+            ("1.20.1-forge-15.2.0", "15.2.0"),
+            ("mc1.20-0.5.3", "0.5.3"),
+            ("3.1.0+1.21-fabric", "3.1.0"),
+            ("3.1.0-mc1.20.1", "3.1.0"),
+            ("1.2.3-fabric", "1.2.3"),
+            // Leading MC-like tokens that are the mod version.
+            ("1.12.0-2", "1.12.0-2"),
+            ("1.9.1-beta-1.21", "1.9.1-beta"),
+            ("1.19.2-rc1-1.20", "1.19.2-rc1"),
+            // Nothing numeric left: compared as written.
+            ("1.20.1", "1.20.1"),
+            ("fabric-1.20.1", "fabric-1.20.1"),
+        ];
+        for (version, expected) in cases {
+            assert_eq!(
+                super::normalize_version(version, &all),
+                expected,
+                "{version}"
+            );
+        }
+
+        let none = super::Normalization {
+            build_metadata: false,
+            mc_version: false,
+            loader: false,
+        };
+        assert_eq!(
+            super::normalize_version("1.20.1-1.0.0+fabric", &none),
+            "1.20.1-1.0.0+fabric"
+        );
+    }
+
+    #[test]
+    fn normalize_distinguishes_real_updates() {
+        let all = super::Normalization::default();
+        let differ = [
+            ("1.12.0-2", "1.12.1-2"),
+            ("1.9.1-beta-1.21", "1.9.2-beta-1.21"),
+            ("1.19.2-rc1-1.20", "1.19.2-rc2-1.20"),
+            ("1.20.1-1.0.0", "1.20.1-1.0.1"),
+        ];
+        for (a, b) in differ {
+            assert_ne!(
+                super::normalize_version(a, &all),
+                super::normalize_version(b, &all),
+                "{a} vs {b}"
+            );
+        }
+        let same = [
+            ("3.1.0+1.21-fabric", "3.1.0+1.21.1-fabric"),
+            ("1.20.1-1.0.0", "1.21-1.0.0"),
+            ("2.0.0-fabric", "2.0.0-forge"),
+        ];
+        for (a, b) in same {
+            assert_eq!(
+                super::normalize_version(a, &all),
+                super::normalize_version(b, &all),
+                "{a} vs {b}"
+            );
+        }
+    }
+
+    #[test]
+    fn matches_range_table() {
+        let cases = [
+            ("1.2.3", "", Some(true)),
+            ("1.2.3", "*", Some(true)),
+            ("1.2.3", ">=1.2", Some(true)),
+            ("1.1.9", ">=1.2", Some(false)),
+            ("1.9.0", "<2", Some(true)),
+            ("2.0.0", "<2", Some(false)),
+            ("1.5.0", "^1.2", Some(true)),
+            ("2.0.0", "^1.2", Some(false)),
+            ("1.2.9", "~1.2.3", Some(true)),
+            ("1.3.0", "~1.2.3", Some(false)),
+            ("1.20.4", "1.20.x", Some(true)),
+            ("1.21", "1.20.x", Some(false)),
+            ("1.5.0", ">=1.2 <2", Some(true)),
+            ("2.5.0", ">=1.2 <2", Some(false)),
+            ("3.0.0", "<2 || >=3", Some(true)),
+            ("2.5.0", "<2 || >=3", Some(false)),
+            ("1.2.3", "=1.2.3", Some(true)),
+            ("1.2.3", "1.2.4", Some(false)),
+            ("1.0.0-beta", ">=1.0.0", Some(false)),
+            ("1.0.0-fabric", ">=1.0.0", Some(true)),
+            ("1.20.1-1.0.0", ">=1.0.0 <2", Some(true)),
+            ("1.5", "[1.0,2.0)", Some(true)),
+            ("abc", ">=1.0", None),
+            ("1.0", ">=abc", None),
+        ];
+        for (version, range, expected) in cases {
+            assert_eq!(
+                super::matches_range(version, range),
+                expected,
+                "{version} in {range:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn matches_maven_range_table() {
+        let cases = [
+            ("1.0", "[1.0,2.0)", Some(true)),
+            ("2.0", "[1.0,2.0)", Some(false)),
+            ("1.0", "(1.0,2.0]", Some(false)),
+            ("2.0", "(1.0,2.0]", Some(true)),
+            ("1.4", "(,1.5]", Some(true)),
+            ("1.6", "(,1.5]", Some(false)),
+            ("47.2.0", "[47,)", Some(true)),
+            ("46.0.1", "[47,)", Some(false)),
+            ("1.2", "[1.2]", Some(true)),
+            ("1.2.1", "[1.2]", Some(false)),
+            ("3.5", "[1.0,2.0),[3.0,)", Some(true)),
+            ("2.5", "[1.0,2.0),[3.0,)", Some(false)),
+            ("abc", "[1.0,2.0)", None),
+            ("1.0", "[abc,2.0)", None),
+            ("1.0", "[1.0,2.0", None),
+            // Malformed ranges from real metadata must not panic; a
+            // version outside the first set reaches the broken tail.
+            ("1.0", "[1.0,2.0)]", Some(true)),
+            ("2.5", "[1.0,2.0)]", None),
+            ("2.5", "[1.0,2.0),)", None),
+            ("2.5", "[1.0,2.0)é]", None),
+        ];
+        for (version, range, expected) in cases {
+            assert_eq!(
+                super::matches_maven_range(version, range),
+                expected,
+                "{version} in {range}"
+            );
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! Regression corpus for the lenient metadata JSON reader. Every
+//! `tests/corpus/lenient_json/<case>.json` is a malformed metadata file;
+//! `<case>.expected.json` holds the repairs it must report and the value the
+//! repaired text must parse to. The current cases are reduced reproductions
+//! of the defects seen in mod jars, not verbatim copies. Real manifests go in
+//! as `<modid>-<version>.json`.
+
+#[path = "../src/lenient_json.rs"]
+mod lenient_json;
+
+use std::path::Path;
+
+#[test]
+fn corpus() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus/lenient_json");
+    let mut cases: Vec<_> = std::fs::read_dir(&dir)
+        .expect("corpus directory")
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            let name = p.file_name().unwrap_or_default().to_string_lossy();
+            name.ends_with(".json") && !name.ends_with(".expected.json")
+        })
+        .collect();
+    cases.sort();
+    assert!(!cases.is_empty(), "no corpus files in {}", dir.display());
+
+    for case in cases {
+        let input = std::fs::read_to_string(&case).unwrap();
+        let expected = std::fs::read_to_string(case.with_extension("expected.json")).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(&expected).unwrap();
+
+        let repaired = lenient_json::repair(&input);
+        let value: serde_json::Value = serde_json::from_str(&repaired.text)
+            .unwrap_or_else(|e| panic!("{}: {e}\n{}", case.display(), repaired.text));
+        let repairs = serde_json::to_value(&repaired.repairs).unwrap();
+
+        assert_eq!(value, expected["value"], "{}: value", case.display());
+        assert_eq!(repairs, expected["repairs"], "{}: repairs", case.display());
+    }
+}